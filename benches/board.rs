@@ -1,4 +1,4 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use rust_reversi_core::board::Board;
 
 fn play_game() {
@@ -13,8 +13,85 @@ fn play_game() {
     }
 }
 
+// positions from random games, used for the move generation benchmarks
+fn sample_boards() -> Vec<Board> {
+    let mut boards = Vec::new();
+    for _ in 0..20 {
+        let mut board = Board::new();
+        while !board.is_game_over() {
+            boards.push(board.clone());
+            if board.is_pass() {
+                board.do_pass().unwrap();
+            } else {
+                let m = board.get_random_move().unwrap();
+                board.do_move(m).unwrap();
+            }
+        }
+    }
+    boards
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("play_game", |b| b.iter(play_game));
+
+    let board = Board::from_transcript("f5d6c3d3c4f4f6f3e6e7").unwrap();
+    c.bench_function("child_boards", |b| {
+        b.iter(|| {
+            let mut board = black_box(board.clone());
+            board.get_child_boards().unwrap().len()
+        })
+    });
+    c.bench_function("child_boards_iter", |b| {
+        b.iter(|| {
+            let mut board = black_box(board.clone());
+            board.get_child_boards_iter().count()
+        })
+    });
+
+    // with the simd feature, get_legal_moves and get_flips_bit use AVX2 if available
+    let boards = sample_boards();
+    c.bench_function("legal_moves", |b| {
+        b.iter(|| {
+            boards
+                .iter()
+                .map(|board| {
+                    let mut board = black_box(board).clone();
+                    board.get_legal_moves()
+                })
+                .fold(0, |acc, m| acc ^ m)
+        })
+    });
+    c.bench_function("legal_moves_scalar", |b| {
+        b.iter(|| {
+            boards
+                .iter()
+                .map(|board| black_box(board).get_legal_moves_scalar())
+                .fold(0, |acc, m| acc ^ m)
+        })
+    });
+    let moves: Vec<(Board, u64)> = boards
+        .iter()
+        .map(|board| {
+            let legal_moves = board.get_legal_moves_scalar();
+            (board.clone(), legal_moves & legal_moves.wrapping_neg())
+        })
+        .collect();
+    c.bench_function("flips", |b| {
+        b.iter(|| {
+            moves
+                .iter()
+                .map(|(board, pos)| black_box(board).get_flips_bit(*pos))
+                .fold(0, |acc, f| acc ^ f)
+        })
+    });
+    c.bench_function("flips_scalar", |b| {
+        b.iter(|| {
+            moves
+                .iter()
+                .map(|(board, pos)| black_box(board).get_flips_bit_scalar(*pos))
+                .fold(0, |acc, f| acc ^ f)
+        })
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
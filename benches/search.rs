@@ -9,6 +9,7 @@ use rust_reversi_core::search::Evaluator;
 use rust_reversi_core::search::LegalNumEvaluator;
 use rust_reversi_core::search::MatrixEvaluator;
 use rust_reversi_core::search::PieceEvaluator;
+use rust_reversi_core::search::PositionGenerator;
 use rust_reversi_core::search::{AlphaBetaSearch, Search};
 
 const EPSILON: f64 = 1e-2;
@@ -29,6 +30,20 @@ fn play_with_search(search: &dyn Search) {
     }
 }
 
+fn search_positions(search: &dyn Search, boards: &[Board]) {
+    for board in boards {
+        let mut board = board.clone();
+        black_box(search.get_move(&mut board));
+    }
+}
+
+fn get_positions(n_empties: usize) -> Vec<Board> {
+    let mut generator = PositionGenerator::new(n_empties, 0);
+    generator.set_balance_evaluator(Some(Arc::new(PieceEvaluator::new())));
+    generator.set_max_imbalance(16);
+    generator.generate_n(100)
+}
+
 fn get_alpha_beta4_piece() -> AlphaBetaSearch {
     AlphaBetaSearch::new(4, Arc::new(PieceEvaluator::new()), 1 << 10)
 }
@@ -180,6 +195,35 @@ fn criterion_benchmark(c: &mut Criterion) {
         b.iter(|| play_with_search(&alpha_beta4_bitmatrix10s))
     });
 
+    let midgame_positions = get_positions(30);
+    let endgame_positions = get_positions(12);
+    c.bench_function("alpha_beta4_matrix_midgame", |b| {
+        b.iter(|| search_positions(&alpha_beta4_matrix, &midgame_positions))
+    });
+    c.bench_function("alpha_beta4_matrix_endgame", |b| {
+        b.iter(|| search_positions(&alpha_beta4_matrix, &endgame_positions))
+    });
+    let mut alpha_beta4_matrix_pvs = get_alpha_beta4_matrix();
+    alpha_beta4_matrix_pvs.set_pvs(true);
+    c.bench_function("alpha_beta4_matrix_pvs_midgame", |b| {
+        b.iter(|| search_positions(&alpha_beta4_matrix_pvs, &midgame_positions))
+    });
+    c.bench_function("alpha_beta4_matrix_pvs_endgame", |b| {
+        b.iter(|| search_positions(&alpha_beta4_matrix_pvs, &endgame_positions))
+    });
+
+    // Lazy SMP with all the cores against a single thread
+    let n_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let mut alpha_beta6_matrix = get_alpha_beta4_matrix();
+    alpha_beta6_matrix.set_max_depth(6);
+    c.bench_function("alpha_beta6_matrix_midgame", |b| {
+        b.iter(|| search_positions(&alpha_beta6_matrix, &midgame_positions[..10]))
+    });
+    alpha_beta6_matrix.set_n_threads(n_threads);
+    c.bench_function("alpha_beta6_matrix_smp_midgame", |b| {
+        b.iter(|| search_positions(&alpha_beta6_matrix, &midgame_positions[..10]))
+    });
+
     let mcts_100_1_10 = rust_reversi_core::search::MctsSearch::new(100, 1.0, 10);
 
     c.bench_function("mcts: 100-1.0-10", |b| {
@@ -187,6 +231,20 @@ fn criterion_benchmark(c: &mut Criterion) {
             play_with_search(&mcts_100_1_10);
         })
     });
+
+    let mut mcts_1000_1_10 = rust_reversi_core::search::MctsSearch::new(1000, 1.0, 10);
+    let mut board = Board::new();
+    c.bench_function("mcts: 1000-1.0-10", |b| {
+        b.iter(|| mcts_1000_1_10.search(&mut board))
+    });
+    mcts_1000_1_10.set_n_threads(n_threads);
+    c.bench_function("mcts: 1000-1.0-10 tree parallel", |b| {
+        b.iter(|| mcts_1000_1_10.search(&mut board))
+    });
+    mcts_1000_1_10.set_parallel_mode(rust_reversi_core::search::MctsParallelMode::Root);
+    c.bench_function("mcts: 1000-1.0-10 root parallel", |b| {
+        b.iter(|| mcts_1000_1_10.search(&mut board))
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -1,5 +1,6 @@
 use crate::arena::error::{ArenaError, GameError, PlayerError};
-use crate::board::{Board, BoardError, Turn};
+use crate::arena::record::{GameRecord, Termination};
+use crate::board::{Board, BoardError, Move, Square, Turn};
 use indicatif::{MultiProgress, ProgressBar, ProgressState, ProgressStyle};
 use std::io::{BufRead, Write};
 use std::sync::mpsc;
@@ -53,12 +54,7 @@ where
             let result = stdout
                 .read_line(&mut response)
                 .map_err(|_| PlayerError::Io)
-                .and_then(|_| {
-                    response
-                        .trim()
-                        .parse::<usize>()
-                        .map_err(|_| PlayerError::Parse)
-                });
+                .and_then(|_| parse_response(response.trim()));
             tx.send(result).unwrap();
         });
 
@@ -73,6 +69,34 @@ where
             }
         }
     }
+
+    /// Get the move of the player as a Move
+    /// # Returns
+    /// * `Result<Move, PlayerError>` - Move if successful, Err(PlayerError) otherwise
+    /// # Note
+    /// * Same as `get_move_with_timeout`, but a position out of the board is a parse error
+    /// * Players are not asked when they must pass, so the move is never a pass
+    pub fn get_typed_move_with_timeout(
+        &mut self,
+        board: &Board,
+        timeout: Duration,
+    ) -> Result<Move, PlayerError> {
+        let pos = self.get_move_with_timeout(board, timeout)?;
+        Square::new(pos)
+            .map(Move::Place)
+            .map_err(|_| PlayerError::Parse)
+    }
+}
+
+// players may answer with a position (0..64) or a square such as "f5"
+fn parse_response(response: &str) -> Result<usize, PlayerError> {
+    match response.parse::<usize>() {
+        Ok(pos) => Ok(pos),
+        Err(_) => response
+            .parse::<Square>()
+            .map(usize::from)
+            .map_err(|_| PlayerError::Parse),
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -94,6 +118,7 @@ where
     R: BufRead + Send + 'static,
 {
     board: Board,
+    initial_board: Board,
     black_player: &'a mut Player<W, R>,
     white_player: &'a mut Player<W, R>,
     moves: Vec<Option<usize>>,
@@ -106,9 +131,14 @@ where
     W: Write + Send + 'static,
     R: BufRead + Send + 'static,
 {
-    fn new(black_player: &'a mut Player<W, R>, white_player: &'a mut Player<W, R>) -> Self {
+    fn new(
+        black_player: &'a mut Player<W, R>,
+        white_player: &'a mut Player<W, R>,
+        board: Board,
+    ) -> Self {
         Game {
-            board: Board::new(),
+            initial_board: board.clone(),
+            board,
             black_player,
             white_player,
             moves: Vec::new(),
@@ -117,14 +147,14 @@ where
         }
     }
 
-    fn get_move(&mut self) -> Result<usize, GameError> {
+    fn get_move(&mut self) -> Result<Move, GameError> {
         let turn = self.board.get_turn();
         let player = match turn {
             Turn::Black => &mut self.black_player,
             Turn::White => &mut self.white_player,
         };
         player
-            .get_move_with_timeout(&self.board, DEFAULT_TIMEOUT)
+            .get_typed_move_with_timeout(&self.board, DEFAULT_TIMEOUT)
             .map_err(|e| match e {
                 PlayerError::Io => match turn {
                     Turn::Black => GameError::BlackCrash,
@@ -150,7 +180,7 @@ where
                 continue;
             }
             let mv = self.get_move()?;
-            self.board.do_move(mv).map_err(|e| match e {
+            self.board.play_move(mv).map_err(|e| match e {
                 BoardError::InvalidMove => match self.board.get_turn() {
                     Turn::Black => GameError::BlackInvalidMove,
                     Turn::White => GameError::WhiteInvalidMove,
@@ -161,7 +191,7 @@ where
                 },
                 _ => GameError::UnexpectedError,
             })?;
-            self.moves.push(Some(mv));
+            self.moves.push(mv.into());
             self.board_log.push(self.board.get_board());
         }
 
@@ -186,21 +216,124 @@ where
             GameStatus::Playing => Err(GameError::GameNotOverYet),
         }
     }
+
+    fn get_record(&self, players: (String, String), termination: Termination) -> GameRecord {
+        GameRecord::new(
+            players,
+            &self.initial_board,
+            self.moves.clone(),
+            &self.board,
+            termination,
+        )
+    }
 }
 
+#[derive(Debug, Clone)]
 pub enum PlayerOrder {
     P1equalsBlack,
     P2equalsBlack,
 }
 type PlayerPair<W, R> = Arc<Mutex<(Player<W, R>, Player<W, R>)>>;
 
+// Game finished in `play_boards`, with the index of its board
+struct PlayedGame {
+    board_index: usize,
+    order: PlayerOrder,
+    result: GameResult,
+}
+
+/// Results of the games played from one opening
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpeningStats {
+    stats: (usize, usize, usize),
+    pieces: (usize, usize),
+}
+
+impl OpeningStats {
+    fn add(&mut self, order: &PlayerOrder, result: &GameResult) {
+        let (p1_win, p2_win, draw) = get_game_stats(order, result);
+        let (p1_pieces, p2_pieces) = get_game_pieces(order, result);
+        self.stats.0 += p1_win;
+        self.stats.1 += p2_win;
+        self.stats.2 += draw;
+        self.pieces.0 += p1_pieces;
+        self.pieces.1 += p2_pieces;
+    }
+
+    /// Get the statistics of the games played from the opening
+    /// # Returns
+    /// * `(usize, usize, usize)` - Number of wins for player 1, player 2 and draws
+    pub fn get_stats(&self) -> (usize, usize, usize) {
+        self.stats
+    }
+
+    /// Get the number of pieces of the games played from the opening
+    /// # Returns
+    /// * `(usize, usize)` - Number of pieces of player 1 and player 2
+    pub fn get_pieces(&self) -> (usize, usize) {
+        self.pieces
+    }
+}
+
+// Returns (p1_win, p2_win, draw) of a game.
+fn get_game_stats(order: &PlayerOrder, result: &GameResult) -> (usize, usize, usize) {
+    match result {
+        GameResult::BlackWin(_, _) => match order {
+            PlayerOrder::P1equalsBlack => (1, 0, 0),
+            PlayerOrder::P2equalsBlack => (0, 1, 0),
+        },
+        GameResult::WhiteWin(_, _) => match order {
+            PlayerOrder::P1equalsBlack => (0, 1, 0),
+            PlayerOrder::P2equalsBlack => (1, 0, 0),
+        },
+        GameResult::Draw(_, _) => (0, 0, 1),
+    }
+}
+
+// Returns (p1_pieces, p2_pieces) of a game.
+fn get_game_pieces(order: &PlayerOrder, result: &GameResult) -> (usize, usize) {
+    let (black_pieces, white_pieces) = match result {
+        GameResult::BlackWin(black_pieces, white_pieces) => (*black_pieces, *white_pieces),
+        GameResult::WhiteWin(black_pieces, white_pieces) => (*black_pieces, *white_pieces),
+        GameResult::Draw(black_pieces, white_pieces) => (*black_pieces, *white_pieces),
+    };
+    match order {
+        PlayerOrder::P1equalsBlack => (black_pieces, white_pieces),
+        PlayerOrder::P2equalsBlack => (white_pieces, black_pieces),
+    }
+}
+
+fn new_progress_bar(m: &Option<MultiProgress>, len: usize) -> Option<ProgressBar> {
+    match m {
+        Some(m) => {
+            let pb = m.add(ProgressBar::new(len as u64));
+            pb.set_style(
+                ProgressStyle::with_template("[{wide_bar}] [{elapsed_precise}] ({eta})")
+                    .unwrap()
+                    .with_key(
+                        "eta",
+                        |state: &ProgressState, w: &mut dyn std::fmt::Write| {
+                            write!(w, "{:.1}s", state.eta().as_secs_f64()).unwrap()
+                        },
+                    )
+                    .progress_chars("#>-"),
+            );
+            Some(pb)
+        }
+        None => None,
+    }
+}
+
 pub struct Arena<W, R>
 where
     W: Write + Send + 'static,
     R: BufRead + Send + 'static,
 {
     games: Vec<(PlayerOrder, GameResult)>,
+    records: Vec<GameRecord>,
     players: Vec<PlayerPair<W, R>>,
+    player_names: (String, String),
     show_progress: bool,
 }
 
@@ -212,183 +345,155 @@ where
     pub fn new(players: Vec<(Player<W, R>, Player<W, R>)>, show_progress: bool) -> Self {
         Arena {
             games: Vec::new(),
+            records: Vec::new(),
             players: players
                 .into_iter()
                 .map(|(p1, p2)| Arc::new(Mutex::new((p1, p2))))
                 .collect(),
+            player_names: ("player1".to_string(), "player2".to_string()),
             show_progress,
         }
     }
 
+    /// Set the names of the players written in the game records
+    pub fn set_player_names(&mut self, player1: String, player2: String) {
+        self.player_names = (player1, player2);
+    }
+
     pub fn play_n(&mut self, n: usize) -> Result<(), ArenaError> {
         if n % 2 != 0 {
             return Err(ArenaError::GameNumberInvalid);
         }
+        self.play_boards(vec![Board::new(); n / 2]).map(|_| ())
+    }
 
-        let half_n = n / 2;
-        let players0 = Arc::clone(&self.players[0]);
-        let players1 = Arc::clone(&self.players[1]);
+    /// Play each opening twice, once with each player as black
+    /// # Returns
+    /// * `Result<Vec<OpeningStats>, ArenaError>` - Results of this call for each opening
+    ///   in the same order
+    pub fn play_openings(&mut self, openings: &[Board]) -> Result<Vec<OpeningStats>, ArenaError> {
+        let games = self.play_boards(openings.to_vec())?;
+        let mut opening_stats = vec![OpeningStats::default(); openings.len()];
+        for game in games.iter() {
+            opening_stats[game.board_index].add(&game.order, &game.result);
+        }
+        Ok(opening_stats)
+    }
 
+    // Play each board twice, once with each player as black.
+    // If a game fails, the results of all games of this call are dropped,
+    // but the records of the games played so far are kept.
+    fn play_boards(&mut self, boards: Vec<Board>) -> Result<Vec<PlayedGame>, ArenaError> {
         let m = match self.show_progress {
             true => Some(MultiProgress::new()),
             false => None,
         };
-        let pb1 = match m {
-            Some(ref m) => {
-                let pb = m.add(ProgressBar::new(half_n as u64));
-                pb.set_style(
-                    ProgressStyle::with_template("[{wide_bar}] [{elapsed_precise}] ({eta})")
-                        .unwrap()
-                        .with_key(
-                            "eta",
-                            |state: &ProgressState, w: &mut dyn std::fmt::Write| {
-                                write!(w, "{:.1}s", state.eta().as_secs_f64()).unwrap()
-                            },
-                        )
-                        .progress_chars("#>-"),
-                );
-                Some(pb)
-            }
-            None => None,
-        };
-        let pb2 = match m {
-            Some(ref m) => {
-                let pb = m.add(ProgressBar::new(half_n as u64));
-                pb.set_style(
-                    ProgressStyle::with_template("[{wide_bar}] [{elapsed_precise}] ({eta})")
-                        .unwrap()
-                        .with_key(
-                            "eta",
-                            |state: &ProgressState, w: &mut dyn std::fmt::Write| {
-                                write!(w, "{:.1}s", state.eta().as_secs_f64()).unwrap()
-                            },
-                        )
-                        .progress_chars("#>-"),
-                );
-                Some(pb)
-            }
-            None => None,
-        };
 
         let mut handles = vec![];
-        // p1equalsBlack
+        for (players, order) in self
+            .players
+            .iter()
+            .zip([PlayerOrder::P1equalsBlack, PlayerOrder::P2equalsBlack])
         {
-            handles.push(thread::spawn(move || {
-                let mut results = Vec::with_capacity(half_n);
-                for _ in 0..half_n {
-                    let mut player0 = players0.lock().unwrap();
-                    let (p1, p2) = &mut *player0;
-                    let mut game = Game::new(p1, p2);
-                    match game.play() {
-                        Ok(_) => {
-                            let result = game.get_result();
-                            results.push((PlayerOrder::P1equalsBlack, result));
-                        }
-                        Err(e) => return Err(ArenaError::GameError(e)),
-                    }
-                    if let Some(ref pb1) = pb1 {
-                        pb1.inc(1);
-                    }
+            let players = Arc::clone(players);
+            let boards = boards.clone();
+            let names = match order {
+                PlayerOrder::P1equalsBlack => self.player_names.clone(),
+                PlayerOrder::P2equalsBlack => {
+                    (self.player_names.1.clone(), self.player_names.0.clone())
                 }
-                Ok(results)
-            }));
-        }
-
-        // p2equalsBlack
-        {
+            };
+            let pb = new_progress_bar(&m, boards.len());
             handles.push(thread::spawn(move || {
-                let mut results = Vec::with_capacity(half_n);
-                for _ in 0..half_n {
-                    let mut player1 = players1.lock().unwrap();
-                    let (p1, p2) = &mut *player1;
-                    let mut game = Game::new(p1, p2);
-                    match game.play() {
-                        Ok(_) => {
-                            let result = game.get_result();
-                            results.push((PlayerOrder::P2equalsBlack, result));
+                let mut results = Vec::with_capacity(boards.len());
+                let mut records = Vec::with_capacity(boards.len());
+                for (i, board) in boards.into_iter().enumerate() {
+                    let mut players = players.lock().unwrap();
+                    let (black, white) = &mut *players;
+                    let mut game = Game::new(black, white, board);
+                    let (result, error) = match game.play().and_then(|_| game.get_result()) {
+                        Ok(result) => (Some(result), None),
+                        Err(e) => (None, Some(e)),
+                    };
+                    let termination = match &error {
+                        Some(e) => Termination::from(e),
+                        None => Termination::GameOver,
+                    };
+                    records.push(game.get_record(names.clone(), termination));
+                    match (result, error) {
+                        (Some(result), _) => results.push(PlayedGame {
+                            board_index: i,
+                            order: order.clone(),
+                            result,
+                        }),
+                        (None, Some(e)) => {
+                            return (results, records, Some(ArenaError::GameError(e)))
                         }
-                        Err(e) => return Err(ArenaError::GameError(e)),
+                        (None, None) => unreachable!(),
                     }
-                    if let Some(ref pb2) = pb2 {
-                        pb2.inc(1);
+                    if let Some(ref pb) = pb {
+                        pb.inc(1);
                     }
                 }
-                Ok(results)
+                (results, records, None)
             }));
         }
 
-        let mut all_results = Vec::with_capacity(n);
+        let mut games = Vec::new();
+        let mut error = None;
         for handle in handles {
             match handle.join() {
-                Ok(Ok(mut results)) => {
-                    for result in results.drain(..) {
-                        match result {
-                            (order, Ok(result)) => all_results.push((order, result)),
-                            (_, Err(e)) => {
-                                return Err(ArenaError::GameError(e));
-                            }
-                        }
-                    }
+                Ok((results, records, e)) => {
+                    games.extend(results);
+                    self.records.extend(records);
+                    error = error.or(e);
                 }
-                Ok(Err(e)) => return Err(e),
-                Err(_) => return Err(ArenaError::ThreadJoinError),
+                Err(_) => error = error.or(Some(ArenaError::ThreadJoinError)),
             }
         }
-        self.games.extend(all_results);
-        Ok(())
+        if let Some(e) = error {
+            return Err(e);
+        }
+        self.games.extend(
+            games
+                .iter()
+                .map(|game| (game.order.clone(), game.result.clone())),
+        );
+        Ok(games)
     }
 
+    /// Get the statistics of the games played
+    /// # Note
+    /// * Games of a call that returned an error are not counted
     pub fn get_stats(&self) -> (usize, usize, usize) {
-        let mut p1_win = 0;
-        let mut p2_win = 0;
-        let mut draw = 0;
-        for (order, game_result) in self.games.iter() {
-            match game_result {
-                GameResult::BlackWin(_, _) => match order {
-                    PlayerOrder::P1equalsBlack => p1_win += 1,
-                    PlayerOrder::P2equalsBlack => p2_win += 1,
-                },
-                GameResult::WhiteWin(_, _) => match order {
-                    PlayerOrder::P1equalsBlack => p2_win += 1,
-                    PlayerOrder::P2equalsBlack => p1_win += 1,
-                },
-                GameResult::Draw(_, _) => draw += 1,
-            }
+        let mut stats = (0, 0, 0);
+        for (order, result) in self.games.iter() {
+            let (p1_win, p2_win, draw) = get_game_stats(order, result);
+            stats.0 += p1_win;
+            stats.1 += p2_win;
+            stats.2 += draw;
         }
-        (p1_win, p2_win, draw)
+        stats
     }
 
+    /// Get the number of pieces of the games played
+    /// # Note
+    /// * Games of a call that returned an error are not counted
     pub fn get_pieces(&self) -> (usize, usize) {
-        let mut p1_pieces = 0;
-        let mut p2_pieces = 0;
-        for (order, game_result) in self.games.iter() {
-            match game_result {
-                GameResult::BlackWin(black_pieces, white_pieces) => match order {
-                    PlayerOrder::P1equalsBlack => {
-                        p1_pieces += black_pieces;
-                        p2_pieces += white_pieces;
-                    }
-                    PlayerOrder::P2equalsBlack => {
-                        p1_pieces += white_pieces;
-                        p2_pieces += black_pieces;
-                    }
-                },
-                GameResult::WhiteWin(black_pieces, white_pieces) => match order {
-                    PlayerOrder::P1equalsBlack => {
-                        p2_pieces += white_pieces;
-                        p1_pieces += black_pieces;
-                    }
-                    PlayerOrder::P2equalsBlack => {
-                        p2_pieces += black_pieces;
-                        p1_pieces += white_pieces;
-                    }
-                },
-                GameResult::Draw(black_pieces, white_pieces) => {
-                    p1_pieces += black_pieces;
-                    p2_pieces += white_pieces;
-                }
-            }
+        let mut pieces = (0, 0);
+        for (order, result) in self.games.iter() {
+            let (p1_pieces, p2_pieces) = get_game_pieces(order, result);
+            pieces.0 += p1_pieces;
+            pieces.1 += p2_pieces;
         }
-        (p1_pieces, p2_pieces)
+        pieces
+    }
+
+    /// Get the records of the games played
+    /// # Note
+    /// * If a game was stopped by an error, its record and the records of the games
+    ///   played before it are also included
+    pub fn get_records(&self) -> Vec<GameRecord> {
+        self.records.clone()
     }
 }
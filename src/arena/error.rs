@@ -23,6 +23,7 @@ pub enum ArenaError {
     EngineStartError,
     EngineEndError,
     GameNumberInvalid,
+    InvalidOpening,
     ThreadJoinError,
     GameError(GameError),
 }
@@ -1,6 +1,9 @@
-use crate::arena::core::{Arena, Player};
+use crate::arena::core::{Arena, OpeningStats, Player};
 use crate::arena::error::ArenaError;
-use crate::board::Turn;
+use crate::arena::record::{write_records, GameRecord};
+use crate::board::{Board, Turn};
+use std::fs::File;
+use std::path::Path;
 use std::process::Stdio;
 use std::{
     io::{BufRead, BufReader, Write},
@@ -13,6 +16,7 @@ pub struct LocalArena {
     command2: Vec<String>,
     stats: (usize, usize, usize),
     pieces: (usize, usize),
+    records: Vec<GameRecord>,
     show_progress: bool,
 }
 
@@ -48,6 +52,7 @@ impl LocalArena {
             command2,
             stats: (0, 0, 0),
             pieces: (0, 0),
+            records: Vec::new(),
             show_progress,
         }
     }
@@ -72,23 +77,19 @@ impl LocalArena {
         let stdout = process.stdout.take().unwrap();
 
         // ping-pong test
-        writeln!(stdin, "ping")
-            .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "Write error"))?;
+        writeln!(stdin, "ping").map_err(|_| std::io::Error::other("Write error"))?;
         stdin
             .flush()
-            .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "Flush error"))?;
+            .map_err(|_| std::io::Error::other("Flush error"))?;
 
         let mut reader = BufReader::new(stdout);
         let mut response = String::new();
         reader
             .read_line(&mut response)
-            .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "Read error"))?;
+            .map_err(|_| std::io::Error::other("Read error"))?;
 
         if response.trim() != "pong" {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Invalid response",
-            ));
+            return Err(std::io::Error::other("Invalid response"));
         }
 
         Ok((process, stdin, reader))
@@ -135,18 +136,81 @@ impl LocalArena {
     /// # Note
     /// * n should be a positive even number
     pub fn play_n(&mut self, n: usize) -> Result<(), ArenaError> {
+        self.play(|arena| arena.play_n(n).map(|_| Vec::new()))
+            .map(|_| ())
+    }
+
+    /// Play each opening twice between the two players, swapping colors
+    /// # Arguments
+    /// * `openings` - Starting positions of the games
+    /// # Returns
+    /// * `Result<Vec<OpeningStats>, ArenaError>` - Results for each opening in the same order
+    /// # Example
+    /// ```no_run
+    /// use rust_reversi_core::arena::LocalArena;
+    /// use rust_reversi_core::board::Board;
+    /// let command1 = vec!["./player1".to_string()];
+    /// let command2 = vec!["./player2".to_string()];
+    /// let mut arena = LocalArena::new(command1, command2, false);
+    /// let openings = vec![
+    ///     Board::from_transcript("f5d6c3").unwrap(),
+    ///     Board::from_transcript("f5f6e6").unwrap(),
+    /// ];
+    /// let results = arena.play_openings(&openings).unwrap();
+    /// for (opening, result) in openings.iter().zip(results.iter()) {
+    ///     println!("{}{:?}", opening, result.get_stats());
+    /// }
+    /// ```
+    /// # Note
+    /// * Stats and pieces of the games are also added to `get_stats` and `get_pieces`
+    /// * The results are of this call only, not of the openings played by earlier calls
+    /// * Use `Board::from_transcript` to create openings from transcripts
+    pub fn play_openings(&mut self, openings: &[Board]) -> Result<Vec<OpeningStats>, ArenaError> {
+        self.play(|arena| arena.play_openings(openings))
+    }
+
+    /// Play each opening transcript twice between the two players, swapping colors
+    /// # Arguments
+    /// * `transcripts` - Openings as transcripts such as "f5d6c3"
+    /// # Returns
+    /// * `Result<Vec<OpeningStats>, ArenaError>` - Results for each opening in the same order
+    /// # Note
+    /// * If a transcript is invalid, return Err(ArenaError::InvalidOpening) before any game is played
+    pub fn play_transcripts(
+        &mut self,
+        transcripts: &[&str],
+    ) -> Result<Vec<OpeningStats>, ArenaError> {
+        let openings = transcripts
+            .iter()
+            .map(|t| Board::from_transcript(t).map_err(|_| ArenaError::InvalidOpening))
+            .collect::<Result<Vec<Board>, ArenaError>>()?;
+        self.play_openings(&openings)
+    }
+
+    fn play<F>(&mut self, play_games: F) -> Result<Vec<OpeningStats>, ArenaError>
+    where
+        F: FnOnce(
+            &mut Arena<ChildStdin, BufReader<ChildStdout>>,
+        ) -> Result<Vec<OpeningStats>, ArenaError>,
+    {
         let (mut processes, players) = self.get_players()?;
 
         let mut arena = Arena::new(players, self.show_progress);
-        if let Err(e) = arena.play_n(n) {
-            for (p1, p2) in processes.iter_mut() {
-                p1.kill().map_err(|_| ArenaError::EngineEndError)?;
-                p1.wait().map_err(|_| ArenaError::EngineEndError)?;
-                p2.kill().map_err(|_| ArenaError::EngineEndError)?;
-                p2.wait().map_err(|_| ArenaError::EngineEndError)?;
+        arena.set_player_names(self.command1.join(" "), self.command2.join(" "));
+        let result = play_games(&mut arena);
+        self.records.extend(arena.get_records());
+        let opening_stats = match result {
+            Ok(opening_stats) => opening_stats,
+            Err(e) => {
+                for (p1, p2) in processes.iter_mut() {
+                    p1.kill().map_err(|_| ArenaError::EngineEndError)?;
+                    p1.wait().map_err(|_| ArenaError::EngineEndError)?;
+                    p2.kill().map_err(|_| ArenaError::EngineEndError)?;
+                    p2.wait().map_err(|_| ArenaError::EngineEndError)?;
+                }
+                return Err(e);
             }
-            return Err(e);
-        }
+        };
         let (p1_win, p2_win, draw) = arena.get_stats();
         self.stats.0 += p1_win;
         self.stats.1 += p2_win;
@@ -163,7 +227,7 @@ impl LocalArena {
             p2.wait().map_err(|_| ArenaError::EngineEndError)?;
         }
 
-        Ok(())
+        Ok(opening_stats)
     }
 
     /// Get the statistics of the games played
@@ -173,6 +237,8 @@ impl LocalArena {
     /// * stats are cumulative
     /// * stats are not reset after each call to play_n
     /// * stats are reset after each call to new
+    /// * If a call returns an error, none of its games are added, even those finished
+    ///   before the error, but their records are kept in `get_records`
     pub fn get_stats(&self) -> (usize, usize, usize) {
         self.stats
     }
@@ -184,7 +250,40 @@ impl LocalArena {
     /// * pieces are cumulative
     /// * pieces are not reset after each call to play_n
     /// * pieces are reset after each call to new
+    /// * Same as stats, the games of a call that returned an error are not added
     pub fn get_pieces(&self) -> (usize, usize) {
         self.pieces
     }
+
+    /// Get the records of the games played
+    /// # Returns
+    /// * Records of all games played, grouped by the color of the players
+    /// # Note
+    /// * records are cumulative, like stats
+    /// * If a game was stopped by an error, its record is also included
+    ///   with the termination reason
+    pub fn get_records(&self) -> &[GameRecord] {
+        &self.records
+    }
+
+    /// Write the records of the games played to a file
+    /// # Arguments
+    /// * `path` - Path of the file, overwritten if it exists
+    /// # Returns
+    /// * `Result<(), std::io::Error>` - Ok(()) if successful
+    /// # Example
+    /// ```no_run
+    /// use rust_reversi_core::arena::LocalArena;
+    /// let command1 = vec!["./player1".to_string()];
+    /// let command2 = vec!["./player2".to_string()];
+    /// let mut arena = LocalArena::new(command1, command2, false);
+    /// arena.play_n(10).unwrap();
+    /// arena.write_records("games.txt").unwrap();
+    /// ```
+    /// # Note
+    /// * See `GameRecord` for the format
+    pub fn write_records<P: AsRef<Path>>(&self, path: P) -> Result<(), std::io::Error> {
+        let mut file = File::create(path)?;
+        write_records(&self.records, &mut file)
+    }
 }
@@ -2,7 +2,10 @@ mod core;
 mod error;
 mod local;
 mod network;
+mod record;
+pub use core::OpeningStats;
 pub use error::*;
 pub use local::LocalArena;
 pub use network::NetworkArenaClient;
 pub use network::NetworkArenaServer;
+pub use record::{GameRecord, Termination};
@@ -1,7 +1,9 @@
 use crate::arena::core::{Arena, Player};
 use crate::arena::error::{ClientManagerError, NetworkArenaClientError, NetworkArenaServerError};
+use crate::arena::record::write_records;
 use crate::board::Turn;
 use std::collections::VecDeque;
+use std::fs::OpenOptions;
 use std::io::{self, BufRead, BufReader, Read, Write};
 use std::net::{TcpListener, TcpStream};
 use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
@@ -259,6 +261,15 @@ impl ClientManager {
         Ok(true)
     }
 
+    fn get_names(&self) -> Result<(String, String), ClientManagerError> {
+        let mut names = Vec::with_capacity(2);
+        for stream in self.clients.iter() {
+            let stream = stream.as_ref().ok_or(ClientManagerError::ClientNotExists)?;
+            names.push(stream.peer_addr()?.to_string());
+        }
+        Ok((names[0].clone(), names[1].clone()))
+    }
+
     fn get_players(&self) -> Result<Vec<PlayerPair>, ClientManagerError> {
         let stream1 = self.clients[0]
             .as_ref()
@@ -395,6 +406,7 @@ impl ClientManager {
 pub struct NetworkArenaServer {
     game_per_iter: usize,
     client_manager: ClientManager,
+    record_path: Option<String>,
     show_progress: bool,
 }
 impl NetworkArenaServer {
@@ -418,10 +430,28 @@ impl NetworkArenaServer {
         Ok(NetworkArenaServer {
             game_per_iter,
             client_manager: ClientManager::new(),
+            record_path: None,
             show_progress,
         })
     }
 
+    /// Set the file to write the game records to
+    /// # Arguments
+    /// * `path` - Path of the file, None to stop writing records
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::arena::NetworkArenaServer;
+    /// let mut server = NetworkArenaServer::new(100, true).unwrap();
+    /// server.set_record_path(Some("games.txt".to_string()));
+    /// ```
+    /// # Note
+    /// * Records are appended to the file after each batch
+    /// * Players are named by the addresses of the clients
+    /// * See `GameRecord` for the format
+    pub fn set_record_path(&mut self, path: Option<String>) {
+        self.record_path = path;
+    }
+
     /// Start the server
     /// # Arguments
     /// * `addr` - IP address to bind
@@ -463,7 +493,14 @@ impl NetworkArenaServer {
         }
         let players = self.client_manager.get_players()?;
         let mut arena = Arena::new(players, self.show_progress);
-        arena.play_n(self.game_per_iter)?;
+        let (name1, name2) = self.client_manager.get_names()?;
+        arena.set_player_names(name1, name2);
+        let result = arena.play_n(self.game_per_iter);
+        if let Some(path) = &self.record_path {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            write_records(&arena.get_records(), &mut file)?;
+        }
+        result?;
 
         let (p1_win, p2_win, draw) = arena.get_stats();
         let (p1_pieces, p2_pieces) = arena.get_pieces();
@@ -532,23 +569,19 @@ impl NetworkArenaClient {
         let stdout = process.stdout.take().unwrap();
 
         // ping-pong test
-        writeln!(stdin, "ping")
-            .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "Write error"))?;
+        writeln!(stdin, "ping").map_err(|_| std::io::Error::other("Write error"))?;
         stdin
             .flush()
-            .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "Flush error"))?;
+            .map_err(|_| std::io::Error::other("Flush error"))?;
 
         let mut reader = BufReader::new(stdout);
         let mut response = String::new();
         reader
             .read_line(&mut response)
-            .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "Read error"))?;
+            .map_err(|_| std::io::Error::other("Read error"))?;
 
         if response.trim() != "pong" {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "ping-pong test failed",
-            ));
+            return Err(std::io::Error::other("ping-pong test failed"));
         }
 
         Ok((process, stdin, reader))
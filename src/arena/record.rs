@@ -0,0 +1,276 @@
+use crate::arena::error::GameError;
+use crate::board::{format_transcript, Board, BoardError, Move, Turn};
+use crate::formats::{format_ggf, GgfError, GgfGame, GgfResult, GgfResultType};
+use std::fmt;
+use std::io::{self, Write};
+
+// GGF property keeping the termination, written in the form of `Termination`'s Display
+const TERMINATION_KEY: &str = "C";
+
+/// Reason why a game in the arena ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Termination {
+    /// Neither player can move
+    GameOver,
+    /// The player sent an invalid move
+    InvalidMove(Turn),
+    /// The player did not respond in time
+    Timeout(Turn),
+    /// The player could not be communicated with
+    Crash(Turn),
+    /// The game was stopped by an unexpected error
+    UnexpectedError,
+}
+
+impl From<&GameError> for Termination {
+    fn from(e: &GameError) -> Self {
+        match e {
+            GameError::BlackInvalidMove => Termination::InvalidMove(Turn::Black),
+            GameError::WhiteInvalidMove => Termination::InvalidMove(Turn::White),
+            GameError::BlackTimeout => Termination::Timeout(Turn::Black),
+            GameError::WhiteTimeout => Termination::Timeout(Turn::White),
+            GameError::BlackCrash => Termination::Crash(Turn::Black),
+            GameError::WhiteCrash => Termination::Crash(Turn::White),
+            GameError::GameNotOverYet | GameError::UnexpectedError => Termination::UnexpectedError,
+        }
+    }
+}
+
+impl fmt::Display for Termination {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let color = |turn: &Turn| match turn {
+            Turn::Black => "black",
+            Turn::White => "white",
+        };
+        match self {
+            Termination::GameOver => write!(f, "game over"),
+            Termination::InvalidMove(turn) => write!(f, "{} invalid move", color(turn)),
+            Termination::Timeout(turn) => write!(f, "{} timeout", color(turn)),
+            Termination::Crash(turn) => write!(f, "{} crash", color(turn)),
+            Termination::UnexpectedError => write!(f, "unexpected error"),
+        }
+    }
+}
+
+// Inverse of Display
+fn parse_termination(s: &str) -> Option<Termination> {
+    let turn = |color: &str| match color {
+        "black" => Some(Turn::Black),
+        "white" => Some(Turn::White),
+        _ => None,
+    };
+    match s {
+        "game over" => return Some(Termination::GameOver),
+        "unexpected error" => return Some(Termination::UnexpectedError),
+        _ => {}
+    }
+    let (color, reason) = s.split_once(' ')?;
+    let turn = turn(color)?;
+    match reason {
+        "invalid move" => Some(Termination::InvalidMove(turn)),
+        "timeout" => Some(Termination::Timeout(turn)),
+        "crash" => Some(Termination::Crash(turn)),
+        _ => None,
+    }
+}
+
+/// Record of a game played in the arena
+/// # Note
+/// * Written to files as a GGF game (see `to_ggf`), one game per line
+/// ```text
+/// (;GM[Othello]C[game over]PB[./player1]PW[./player2]RE[+8.000]BO[8 -------- ... *]B[F5]...;)
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameRecord {
+    black_player: String,
+    white_player: String,
+    initial_board: (u64, u64, Turn),
+    moves: Vec<Option<usize>>,
+    black_pieces: usize,
+    white_pieces: usize,
+    termination: Termination,
+}
+
+impl GameRecord {
+    pub(crate) fn new(
+        players: (String, String),
+        initial_board: &Board,
+        moves: Vec<Option<usize>>,
+        final_board: &Board,
+        termination: Termination,
+    ) -> Self {
+        GameRecord {
+            black_player: players.0,
+            white_player: players.1,
+            initial_board: initial_board.get_board(),
+            moves,
+            black_pieces: final_board.black_piece_num() as usize,
+            white_pieces: final_board.white_piece_num() as usize,
+            termination,
+        }
+    }
+
+    /// Get the name of the black player
+    pub fn get_black_player(&self) -> &str {
+        &self.black_player
+    }
+
+    /// Get the name of the white player
+    pub fn get_white_player(&self) -> &str {
+        &self.white_player
+    }
+
+    /// Get the board the game started from
+    pub fn get_initial_board(&self) -> Board {
+        let (player_board, opponent_board, turn) = self.initial_board;
+        Board::from_bitboards_unchecked(player_board, opponent_board, turn)
+    }
+
+    /// Get the moves played in the game
+    /// # Returns
+    /// * List of moves, None for a pass
+    pub fn get_moves(&self) -> &[Option<usize>] {
+        &self.moves
+    }
+
+    /// Get the moves played in the game as Moves
+    /// # Returns
+    /// * `Result<Vec<Move>, BoardError>` - List of moves, Move::Pass for a pass
+    pub fn get_typed_moves(&self) -> Result<Vec<Move>, BoardError> {
+        self.moves.iter().map(|&mv| Move::try_from(mv)).collect()
+    }
+
+    /// Get the moves played in the game as a transcript
+    /// # Returns
+    /// * Transcript such as "f5d6c3d3c4", see `format_transcript`
+    pub fn get_transcript(&self) -> Result<String, BoardError> {
+        format_transcript(&self.moves)
+    }
+
+    /// Get the final score of the game
+    /// # Returns
+    /// * `(usize, usize)` - Number of black pieces and white pieces
+    /// # Note
+    /// * If the game did not end normally, the pieces when the game was stopped
+    pub fn get_score(&self) -> (usize, usize) {
+        (self.black_pieces, self.white_pieces)
+    }
+
+    /// Get the reason why the game ended
+    pub fn get_termination(&self) -> Termination {
+        self.termination
+    }
+
+    /// Get the winner of the game
+    /// # Returns
+    /// * Some(Turn) for the winner, None for a draw or an unexpected error
+    /// # Note
+    /// * If a player made an invalid move, timed out or crashed, the other player wins
+    pub fn get_winner(&self) -> Option<Turn> {
+        match self.termination {
+            Termination::GameOver => match self.black_pieces.cmp(&self.white_pieces) {
+                std::cmp::Ordering::Greater => Some(Turn::Black),
+                std::cmp::Ordering::Less => Some(Turn::White),
+                std::cmp::Ordering::Equal => None,
+            },
+            Termination::InvalidMove(turn)
+            | Termination::Timeout(turn)
+            | Termination::Crash(turn) => Some(turn.opposite()),
+            Termination::UnexpectedError => None,
+        }
+    }
+
+    /// Convert the record to a GGF game
+    /// # Returns
+    /// * `Result<GgfGame, GgfError>` - Game with players (PB, PW), result (RE),
+    ///   setup position (BO), moves and termination (C)
+    /// # Note
+    /// * The result of a finished game is the disc difference from black's point of view
+    /// * A timeout is written as "+64.000:t" or "-64.000:t" for the winner,
+    ///   and an invalid move or a crash as a resignation ("+64.000:r" or "-64.000:r")
+    /// * The result is omitted if the game was stopped by an unexpected error
+    /// * The termination is written as a comment such as "C[black crash]",
+    ///   so that `from_ggf` can tell a crash from an invalid move
+    pub fn to_ggf(&self) -> Result<GgfGame, GgfError> {
+        let mut game = GgfGame::from_moves(&self.get_initial_board(), &self.moves)?;
+        game.set_black_player(Some(self.black_player.clone()));
+        game.set_white_player(Some(self.white_player.clone()));
+        game.set_property(TERMINATION_KEY, &self.termination.to_string())?;
+        let forfeit = |loser: Turn, result_type: GgfResultType| {
+            let score = match loser {
+                Turn::Black => -FORFEIT_SCORE,
+                Turn::White => FORFEIT_SCORE,
+            };
+            GgfResult::new(score, result_type)
+        };
+        let result = match self.termination {
+            Termination::GameOver => Some(GgfResult::new(
+                self.black_pieces as f64 - self.white_pieces as f64,
+                GgfResultType::Normal,
+            )),
+            Termination::Timeout(turn) => Some(forfeit(turn, GgfResultType::Timeout)),
+            Termination::InvalidMove(turn) | Termination::Crash(turn) => {
+                Some(forfeit(turn, GgfResultType::Resigned))
+            }
+            Termination::UnexpectedError => None,
+        };
+        game.set_result(result);
+        Ok(game)
+    }
+
+    /// Create a record from a GGF game written by `to_ggf`
+    /// # Arguments
+    /// * `game` - GGF game with players (PB, PW) and termination (C)
+    /// # Returns
+    /// * `Result<GameRecord, GgfError>` - Record with the score of the board after the moves
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::arena::{GameRecord, Termination};
+    /// use rust_reversi_core::board::Turn;
+    /// use rust_reversi_core::formats::parse_ggf;
+    /// let game = parse_ggf("(;GM[Othello]C[white crash]PB[a]PW[b]RE[+64.000:r]B[F5];)").unwrap();
+    /// let record = GameRecord::from_ggf(&game).unwrap();
+    /// assert_eq!(record.get_termination(), Termination::Crash(Turn::White));
+    /// assert_eq!(record.get_score(), (4, 1));
+    /// ```
+    /// # Note
+    /// * If a player or the termination is missing or malformed, return Err(GgfError::InvalidFormat)
+    pub fn from_ggf(game: &GgfGame) -> Result<GameRecord, GgfError> {
+        let name = |name: Option<&str>| name.map(str::to_string).ok_or(GgfError::InvalidFormat);
+        let termination = game
+            .get_property(TERMINATION_KEY)
+            .and_then(parse_termination)
+            .ok_or(GgfError::InvalidFormat)?;
+        Ok(GameRecord::new(
+            (
+                name(game.get_black_player())?,
+                name(game.get_white_player())?,
+            ),
+            &game.get_initial_board(),
+            game.get_moves().to_vec(),
+            &game.get_board(),
+            termination,
+        ))
+    }
+}
+
+const FORFEIT_SCORE: f64 = 64.0;
+
+/// Write game records in GGF, one game per line
+/// # Arguments
+/// * `records` - Records to write
+/// * `writer` - Destination of the records
+/// # Note
+/// * If a record cannot be converted to GGF, return an error of kind `InvalidData`
+pub(crate) fn write_records<W: Write>(records: &[GameRecord], writer: &mut W) -> io::Result<()> {
+    for record in records {
+        let line = record
+            .to_ggf()
+            .and_then(|game| format_ggf(&game))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        writeln!(writer, "{}", line)?;
+    }
+    writer.flush()
+}
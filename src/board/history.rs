@@ -0,0 +1,156 @@
+use crate::board::{format_transcript, Board, BoardError, UndoRecord};
+
+/// Game with move history supporting undo and redo
+#[derive(Clone, Default)]
+pub struct GameHistory {
+    board: Board,
+    undo_stack: Vec<UndoRecord>,
+    redo_stack: Vec<Option<usize>>,
+}
+
+impl GameHistory {
+    /// Create a new GameHistory instance starting from the initial position
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::GameHistory;
+    /// let mut history = GameHistory::new();
+    /// history.do_move(37).unwrap();
+    /// history.do_move(43).unwrap();
+    /// assert_eq!(history.undo().unwrap(), Some(43));
+    /// assert_eq!(history.redo().unwrap(), Some(43));
+    /// assert_eq!(history.get_transcript().unwrap(), "f5d6");
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new GameHistory instance starting from the given board
+    pub fn from_board(board: Board) -> Self {
+        GameHistory {
+            board,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+        }
+    }
+
+    /// Create a new GameHistory instance by playing a transcript from the initial position
+    /// # Arguments
+    /// * `transcript` - Moves such as "f5d6c3d3c4"
+    /// # Returns
+    /// * `Result<GameHistory, BoardError>` - History of all moves played
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::GameHistory;
+    /// let mut history = GameHistory::from_transcript("f5d6c3").unwrap();
+    /// assert_eq!(history.get_ply(), 3);
+    /// history.undo().unwrap();
+    /// assert_eq!(history.get_transcript().unwrap(), "f5d6");
+    /// ```
+    /// # Note
+    /// * Passes may be omitted, same as `Board::play_transcript`
+    /// * Errors are same as `Board::play_transcript`
+    pub fn from_transcript(transcript: &str) -> Result<GameHistory, BoardError> {
+        let mut history = GameHistory::new();
+        for mv in Board::new().play_transcript(transcript)? {
+            match mv {
+                Some(pos) => history.do_move(pos)?,
+                None => history.do_pass()?,
+            }
+        }
+        Ok(history)
+    }
+
+    /// Get the current board
+    pub fn get_board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Get the board before any move was played
+    pub fn get_initial_board(&self) -> Board {
+        let mut board = self.board.clone();
+        for record in self.undo_stack.iter().rev() {
+            board.undo_move(record);
+        }
+        board
+    }
+
+    /// Get the moves played so far
+    /// # Returns
+    /// * List of moves, None for a pass
+    pub fn get_moves(&self) -> Vec<Option<usize>> {
+        self.undo_stack.iter().map(|r| r.get_pos()).collect()
+    }
+
+    /// Get the moves played so far as a transcript
+    /// # Returns
+    /// * Transcript such as "f5d6c3d3c4", see `format_transcript`
+    pub fn get_transcript(&self) -> Result<String, BoardError> {
+        format_transcript(&self.get_moves())
+    }
+
+    /// Get the number of moves played so far including passes
+    pub fn get_ply(&self) -> usize {
+        self.undo_stack.len()
+    }
+
+    /// Place the stone
+    /// # Arguments
+    /// * `pos` - Position to place the stone
+    /// # Note
+    /// * Errors are same as `Board::do_move`
+    /// * Moves that were undone can no longer be redone
+    pub fn do_move(&mut self, pos: usize) -> Result<(), BoardError> {
+        let record = self.board.do_move_undoable(pos)?;
+        self.undo_stack.push(record);
+        self.redo_stack.clear();
+        Ok(())
+    }
+
+    /// Pass the turn
+    /// # Note
+    /// * Errors are same as `Board::do_pass`
+    /// * Moves that were undone can no longer be redone
+    pub fn do_pass(&mut self) -> Result<(), BoardError> {
+        let record = self.board.do_pass_undoable()?;
+        self.undo_stack.push(record);
+        self.redo_stack.clear();
+        Ok(())
+    }
+
+    /// Get if there is a move to undo
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    /// Get if there is a move to redo
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Undo the last move
+    /// # Returns
+    /// * `Result<Option<usize>, BoardError>` - The move undone, None for a pass
+    /// # Note
+    /// * If there is no move to undo, return Err(BoardError::NothingToUndo)
+    pub fn undo(&mut self) -> Result<Option<usize>, BoardError> {
+        let record = self.undo_stack.pop().ok_or(BoardError::NothingToUndo)?;
+        self.board.undo_move(&record);
+        self.redo_stack.push(record.get_pos());
+        Ok(record.get_pos())
+    }
+
+    /// Redo the last undone move
+    /// # Returns
+    /// * `Result<Option<usize>, BoardError>` - The move redone, None for a pass
+    /// # Note
+    /// * If there is no move to redo, return Err(BoardError::NothingToRedo)
+    pub fn redo(&mut self) -> Result<Option<usize>, BoardError> {
+        let mv = self.redo_stack.pop().ok_or(BoardError::NothingToRedo)?;
+        let record = match mv {
+            Some(pos) => self.board.do_move_undoable(pos)?,
+            None => self.board.do_pass_undoable()?,
+        };
+        self.undo_stack.push(record);
+        Ok(mv)
+    }
+}
@@ -0,0 +1,58 @@
+use crate::board::{Board, BITS};
+use crate::utils::BitIter;
+use std::iter::FusedIterator;
+
+/// Iterator over the legal moves and the boards after them
+/// # Note
+/// * Created by `Board::get_child_boards_iter`
+/// * Moves are yielded in ascending order, same as `Board::get_legal_moves_vec`
+/// * Each child is a copy of the parent board, no heap allocation is made
+#[derive(Clone)]
+pub struct ChildBoards<'a> {
+    board: &'a Board,
+    moves: BitIter,
+}
+
+impl Iterator for ChildBoards<'_> {
+    type Item = (usize, Board);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.moves.next()?;
+        let mut child = self.board.clone();
+        child.apply_move(BITS[pos]);
+        Some((pos, child))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.moves.size_hint()
+    }
+}
+
+impl ExactSizeIterator for ChildBoards<'_> {}
+
+impl FusedIterator for ChildBoards<'_> {}
+
+impl Board {
+    /// Get the legal moves and the boards after them as an iterator
+    /// # Returns
+    /// * Iterator of (position, board after the move)
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::Board;
+    /// let mut board = Board::new();
+    /// for (pos, mut child) in board.get_child_boards_iter() {
+    ///     assert_eq!(child.piece_sum(), 5);
+    ///     assert!(!child.is_legal_move(pos));
+    /// }
+    /// assert_eq!(board.get_child_boards_iter().len(), 4);
+    /// ```
+    /// # Note
+    /// * If the player must pass, the iterator is empty
+    /// * Unlike `get_child_boards`, nothing is allocated on the heap
+    pub fn get_child_boards_iter(&mut self) -> ChildBoards<'_> {
+        let moves = self.get_legal_moves_iter();
+        ChildBoards { board: self, moves }
+    }
+}
@@ -1,12 +1,40 @@
-use crate::utils::StackVec64;
+mod history;
+mod iter;
+mod notation;
+mod perft;
+mod render;
+mod simd;
+mod square;
+mod stability;
+mod symmetry;
+mod validation;
+mod zobrist;
+use crate::utils::{BitIter, StackVec64};
 use core::fmt;
-use std::hash::Hash;
+use rand::Rng;
+use std::hash::{Hash, Hasher};
 use std::mem::swap;
 
-const BOARD_SIZE: usize = 8;
-const LINE_CHAR_BLACK: char = 'X';
-const LINE_CHAR_WHITE: char = 'O';
-const LINE_CHAR_EMPTY: char = '-';
+pub use history::GameHistory;
+pub use iter::ChildBoards;
+pub use notation::{
+    format_move, format_square, format_transcript, parse_move, parse_square, parse_transcript,
+};
+pub use perft::PerftMode;
+pub use render::RenderOptions;
+pub use simd::is_simd_enabled;
+pub use square::{Move, Square};
+pub use symmetry::Transform;
+
+pub(crate) const BOARD_SIZE: usize = 8;
+pub(crate) const LINE_CHAR_BLACK: char = 'X';
+pub(crate) const LINE_CHAR_WHITE: char = 'O';
+pub(crate) const LINE_CHAR_EMPTY: char = '-';
+// Masks of the a and h columns, and of the other columns
+pub(crate) const COL_A: u64 = 0x80_80_80_80_80_80_80_80;
+pub(crate) const COL_H: u64 = 0x01_01_01_01_01_01_01_01;
+pub(crate) const NOT_COL_A: u64 = !COL_A;
+pub(crate) const NOT_COL_H: u64 = !COL_H;
 
 #[derive(Debug)]
 pub enum BoardError {
@@ -17,9 +45,41 @@ pub enum BoardError {
     GameNotOverYet,
     InvalidCharactor,
     NoLegalMove,
+    InvalidNotation,
+    NothingToUndo,
+    NothingToRedo,
+    OverlappingStones,
+    EmptyCenter,
+    DisconnectedStones,
+    InvalidFormat,
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            BoardError::InvalidPosition => "position is out of range",
+            BoardError::InvalidMove => "move is not legal",
+            BoardError::InvalidPass => "pass is not allowed while a move is legal",
+            BoardError::InvalidState => "board state is inconsistent",
+            BoardError::GameNotOverYet => "game is not over yet",
+            BoardError::InvalidCharactor => "invalid character in board string",
+            BoardError::NoLegalMove => "no legal move",
+            BoardError::InvalidNotation => "invalid move notation",
+            BoardError::NothingToUndo => "no move to undo",
+            BoardError::NothingToRedo => "no move to redo",
+            BoardError::OverlappingStones => "a square has both player's and opponent's stones",
+            BoardError::EmptyCenter => "a center square is empty",
+            BoardError::DisconnectedStones => "a stone is not connected to the center",
+            BoardError::InvalidFormat => "malformed board string",
+        };
+        write!(f, "{}", message)
+    }
 }
 
+impl std::error::Error for BoardError {}
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Turn {
     Black,
     White,
@@ -43,6 +103,7 @@ impl Turn {
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Color {
     Empty,
     Black,
@@ -59,12 +120,94 @@ impl Color {
     }
 }
 
-#[derive(Clone, PartialEq, Eq, Hash)]
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "BoardData", into = "BoardData")
+)]
 pub struct Board {
     player_board: u64,
     opponent_board: u64,
     turn: Turn,
     legal_moves_cache: Option<u64>,
+    hash: u64,
+}
+
+// Serialized form of Board, the cache and the hash are recomputed.
+// Overlapping bitboards are rejected, other checks of `validate` are not applied.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BoardData {
+    player_board: u64,
+    opponent_board: u64,
+    turn: Turn,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<BoardData> for Board {
+    type Error = BoardError;
+
+    fn try_from(data: BoardData) -> Result<Self, Self::Error> {
+        if data.player_board & data.opponent_board != 0 {
+            return Err(BoardError::OverlappingStones);
+        }
+        let mut board = Board::new();
+        board.set_board(data.player_board, data.opponent_board, data.turn);
+        Ok(board)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Board> for BoardData {
+    fn from(board: Board) -> Self {
+        BoardData {
+            player_board: board.player_board,
+            opponent_board: board.opponent_board,
+            turn: board.turn,
+        }
+    }
+}
+
+// Equality and hash only depend on the stones and the turn.
+impl PartialEq for Board {
+    fn eq(&self, other: &Self) -> bool {
+        self.player_board == other.player_board
+            && self.opponent_board == other.opponent_board
+            && self.turn == other.turn
+    }
+}
+
+impl Eq for Board {}
+
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+/// Information to undo a move
+/// # Note
+/// * Created by `Board::do_move_undoable` and `Board::do_pass_undoable`
+/// * Used by `Board::undo_move`
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct UndoRecord {
+    pos: Option<usize>,
+    flipped: u64,
+}
+
+impl UndoRecord {
+    /// Get the position of the move
+    /// # Returns
+    /// * Some(position) for a move, None for a pass
+    pub fn get_pos(&self) -> Option<usize> {
+        self.pos
+    }
+
+    /// Get the stones reversed by the move as a bitboard
+    pub fn get_flipped(&self) -> u64 {
+        self.flipped
+    }
 }
 
 const BITS: [u64; 64] = {
@@ -79,11 +222,14 @@ const BITS: [u64; 64] = {
 
 impl Default for Board {
     fn default() -> Self {
+        let player_board = 0x00_00_00_08_10_00_00_00;
+        let opponent_board = 0x00_00_00_10_08_00_00_00;
         Board {
-            player_board: 0x00_00_00_08_10_00_00_00,
-            opponent_board: 0x00_00_00_10_08_00_00_00,
+            player_board,
+            opponent_board,
             turn: Turn::Black,
             legal_moves_cache: None,
+            hash: zobrist::hash(player_board, opponent_board, Turn::Black),
         }
     }
 }
@@ -97,7 +243,7 @@ impl Board {
     /// ```
     /// use rust_reversi_core::board::Board;
     /// let board = Board::new();
-    /// assert_eq!(board.to_string().unwrap(), format!(
+    /// assert_eq!(board.to_string(), format!(
     ///  "{}{}{}{}{}{}{}{}{}{}",
     ///   " |abcdefgh\n",
     ///   "-+--------\n",
@@ -141,16 +287,62 @@ impl Board {
         self.turn
     }
 
+    /// Get the 64-bit hash of the position
+    /// # Returns
+    /// * Zobrist hash of the stones and the turn
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::Board;
+    /// let mut board = Board::new();
+    /// let hash = board.get_hash();
+    /// board.get_legal_moves();
+    /// assert_eq!(board.get_hash(), hash);
+    /// board.do_move(37).unwrap();
+    /// assert_ne!(board.get_hash(), hash);
+    /// ```
+    /// # Note
+    /// * The hash is updated incrementally by `do_move` and `do_pass`
+    /// * Equal positions have the same hash regardless of how they were reached
+    /// * The hash is stable across runs and versions of the same major release
+    pub fn get_hash(&self) -> u64 {
+        self.hash
+    }
+
     /// Set the current board state
     /// # Arguments
     /// * `player_board` - Bitboard of the player's stones
     /// * `opponent_board` - Bitboard of the opponent's stones
     /// * `turn` - Turn of the player
+    /// # Note
+    /// * The board is not validated, use `try_set_board` or `from_bitboards` to validate it
     pub fn set_board(&mut self, player_board: u64, opponent_board: u64, turn: Turn) {
         self.player_board = player_board;
         self.opponent_board = opponent_board;
         self.turn = turn;
         self.legal_moves_cache = None;
+        self.hash = zobrist::hash(player_board, opponent_board, turn);
+    }
+
+    /// Create a new board from bitboards without validation
+    /// # Arguments
+    /// * `player_board` - Bitboard of the player's stones
+    /// * `opponent_board` - Bitboard of the opponent's stones
+    /// * `turn` - Turn of the player
+    /// # Returns
+    /// * `Board` instance, same as `set_board` on a new board
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::Board;
+    /// let (player_board, opponent_board, turn) = Board::new().get_board();
+    /// let board = Board::from_bitboards_unchecked(player_board, opponent_board, turn);
+    /// assert!(board == Board::new());
+    /// ```
+    /// # Note
+    /// * Use `from_bitboards` to validate the position
+    pub fn from_bitboards_unchecked(player_board: u64, opponent_board: u64, turn: Turn) -> Board {
+        let mut board = Board::new();
+        board.set_board(player_board, opponent_board, turn);
+        board
     }
 
     /// Set the current board state from a string
@@ -282,16 +474,13 @@ impl Board {
     /// * third axis: column
     pub fn get_board_matrix(&self) -> Result<Vec<Vec<Vec<i32>>>, BoardError> {
         let mut board_matrix = vec![vec![vec![0; BOARD_SIZE]; BOARD_SIZE]; 3];
-        for x in 0..BOARD_SIZE {
-            for y in 0..BOARD_SIZE {
-                let i = x * BOARD_SIZE + y;
-                let bit = BITS[i];
-                match (self.player_board & bit, self.opponent_board & bit) {
-                    (0, 0) => board_matrix[2][x][y] = 1,
-                    (_, 0) => board_matrix[0][x][y] = 1,
-                    (0, _) => board_matrix[1][x][y] = 1,
-                    (_, _) => return Err(BoardError::InvalidState),
-                }
+        for (i, &bit) in BITS.iter().enumerate() {
+            let (x, y) = (i / BOARD_SIZE, i % BOARD_SIZE);
+            match (self.player_board & bit, self.opponent_board & bit) {
+                (0, 0) => board_matrix[2][x][y] = 1,
+                (_, 0) => board_matrix[0][x][y] = 1,
+                (0, _) => board_matrix[1][x][y] = 1,
+                (_, _) => return Err(BoardError::InvalidState),
             }
         }
         Ok(board_matrix)
@@ -354,32 +543,61 @@ impl Board {
     }
 
     /// Get the legal moves for the player as a bitboard
+    /// # Note
+    /// * With the `simd` feature, AVX2 is used if the CPU supports it (see `is_simd_enabled`)
     pub fn get_legal_moves(&mut self) -> u64 {
         if let Some(legal_moves) = self.legal_moves_cache {
             return legal_moves;
         }
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        let legal_moves = if is_simd_enabled() {
+            // SAFETY: AVX2 is supported by the CPU
+            unsafe { simd::avx2::get_legal_moves(self.player_board, self.opponent_board) }
+        } else {
+            self.get_legal_moves_scalar()
+        };
+        #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+        let legal_moves = self.get_legal_moves_scalar();
+        self.legal_moves_cache = Some(legal_moves);
+        legal_moves
+    }
+
+    /// Get the legal moves for the player as a bitboard without SIMD
+    /// # Note
+    /// * The result is not cached
+    /// * Reference implementation for the SIMD move generation, use `get_legal_moves` instead
+    pub fn get_legal_moves_scalar(&self) -> u64 {
         let mask = 0x7E_7E_7E_7E_7E_7E_7E_7E & self.opponent_board;
-        let legal_moves = (Board::get_legal_partial(mask, self.player_board, 1)
+        (Board::get_legal_partial(mask, self.player_board, 1)
             | Board::get_legal_partial(self.opponent_board, self.player_board, 8)
             | Board::get_legal_partial(mask, self.player_board, 9)
             | Board::get_legal_partial(mask, self.player_board, 7))
-            & !(self.player_board | self.opponent_board);
-        self.legal_moves_cache = Some(legal_moves);
-        legal_moves
+            & !(self.player_board | self.opponent_board)
     }
 
     /// Get the legal moves for the player as a vector of positions
     pub fn get_legal_moves_vec(&mut self) -> StackVec64<usize> {
-        let mut legal_moves = self.get_legal_moves();
         let mut legal_moves_vec = StackVec64::new();
-        while legal_moves != 0 {
-            let lz = legal_moves.leading_zeros();
-            legal_moves_vec.push(lz as usize);
-            legal_moves &= !(1u64 << (63 - lz));
+        for pos in self.get_legal_moves_iter() {
+            legal_moves_vec.push(pos);
         }
         legal_moves_vec
     }
 
+    /// Get the legal moves for the player as an iterator of positions
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::Board;
+    /// let mut board = Board::new();
+    /// let legal_moves: Vec<usize> = board.get_legal_moves_iter().collect();
+    /// assert_eq!(legal_moves, vec![19, 26, 37, 44]);
+    /// ```
+    /// # Note
+    /// * Positions are in ascending order, and nothing is allocated
+    pub fn get_legal_moves_iter(&mut self) -> BitIter {
+        BitIter::new(self.get_legal_moves())
+    }
+
     /// Get the legal moves for the player as a vector of boolean
     /// * true: legal move, false: illegal move
     pub fn get_legal_moves_tf(&mut self) -> Vec<bool> {
@@ -401,22 +619,86 @@ impl Board {
         if self.is_pass() {
             return None;
         }
-        let legal_moves = self.get_legal_moves();
-        let mut child_boards = Vec::with_capacity(legal_moves.count_ones() as usize);
-        for (i, &bit) in BITS.iter().enumerate() {
-            if legal_moves & bit != 0 {
-                let mut child_board = self.clone();
-                child_board.do_move(i).unwrap();
-                child_boards.push(child_board);
-            }
-        }
-        Some(child_boards)
+        Some(
+            self.get_child_boards_iter()
+                .map(|(_, child)| child)
+                .collect(),
+        )
     }
 
     /// Reverse the stones
     /// # Arguments
+    /// * `pos` - Bit of the position to place the stone
+    /// # Returns
+    /// * Bitboard of the reversed stones
+    /// # Note
+    /// * The turn is not changed, use `do_move` to play a move
+    pub fn reverse(&mut self, pos: u64) -> u64 {
+        let reversed = self.get_flips_bit(pos);
+        self.player_board ^= reversed | pos;
+        self.opponent_board ^= reversed;
+        self.hash ^= zobrist::move_diff(pos, reversed, self.turn);
+        self.legal_moves_cache = None;
+        reversed
+    }
+
+    /// Get the stones to be reversed by a move without changing the board
+    /// # Arguments
     /// * `pos` - Position to place the stone
-    pub fn reverse(&mut self, pos: u64) {
+    /// # Returns
+    /// * `Result<u64, BoardError>` - Bitboard of the stones to be reversed
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::Board;
+    /// let board = Board::new();
+    /// let flips = board.get_flips(19).unwrap(); // d3
+    /// assert_eq!(flips, 1u64 << (63 - 27)); // d4
+    /// assert!(board.get_flips(0).is_err());
+    /// ```
+    /// # Note
+    /// * If the move is illegal, return Err(BoardError::InvalidMove)
+    /// * If the position is invalid, return Err(BoardError::InvalidPosition)
+    /// * Use `do_move_undoable` to get the reversed stones of a move actually played
+    pub fn get_flips(&self, pos: usize) -> Result<u64, BoardError> {
+        if pos >= BOARD_SIZE * BOARD_SIZE {
+            return Err(BoardError::InvalidPosition);
+        }
+        let pos_bit = BITS[pos];
+        if (self.player_board | self.opponent_board) & pos_bit != 0 {
+            return Err(BoardError::InvalidMove);
+        }
+        match self.get_flips_bit(pos_bit) {
+            0 => Err(BoardError::InvalidMove),
+            flips => Ok(flips),
+        }
+    }
+
+    /// Get the stones to be reversed by placing a stone on the bit without any check
+    /// # Arguments
+    /// * `pos` - Bit of the position to place the stone
+    /// # Returns
+    /// * Bitboard of the stones to be reversed
+    /// # Note
+    /// * The result is meaningless if the position is not empty
+    /// * With the `simd` feature, AVX2 is used if the CPU supports it (see `is_simd_enabled`)
+    #[inline]
+    pub fn get_flips_bit(&self, pos: u64) -> u64 {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        if is_simd_enabled() {
+            // SAFETY: AVX2 is supported by the CPU
+            return unsafe { simd::avx2::get_flips(self.player_board, self.opponent_board, pos) };
+        }
+        self.get_flips_bit_scalar(pos)
+    }
+
+    /// Get the stones to be reversed by placing a stone on the bit without SIMD
+    /// # Arguments
+    /// * `pos` - Bit of the position to place the stone
+    /// # Returns
+    /// * Bitboard of the stones to be reversed
+    /// # Note
+    /// * Reference implementation for the SIMD move generation, use `get_flips_bit` instead
+    pub fn get_flips_bit_scalar(&self, pos: u64) -> u64 {
         let mut reversed: u64 = 0;
         // tmp is position of stones to reverse if piece exists on the end of stones to reverse
         // mask is position that exists opponent's stone to reverse from piece on each direction
@@ -454,8 +736,7 @@ impl Board {
         get_reverse_r!(0x00_FF_FF_FF_FF_FF_FF_FF, 8); // down
         get_reverse_r!(0x00_7F_7F_7F_7F_7F_7F_7F, 9); // lower right
         get_reverse_r!(0x00_FE_FE_FE_FE_FE_FE_FE, 7); // lower left
-        self.player_board ^= reversed | pos;
-        self.opponent_board ^= reversed;
+        reversed
     }
 
     /// Place the stone
@@ -466,20 +747,11 @@ impl Board {
     /// # Note
     /// * If the move is illegal, return Err(BoardError::InvalidMove)
     /// * If the position is invalid, return Err(BoardError::InvalidPosition)
+    /// * The reversed stones are not returned, so that callers forwarding the result as
+    ///   `Result<(), BoardError>` keep working. Use `do_move_undoable` and
+    ///   `UndoRecord::get_flipped` to get them from the same move, or `get_flips` beforehand
     pub fn do_move(&mut self, pos: usize) -> Result<(), BoardError> {
-        if pos >= BOARD_SIZE * BOARD_SIZE {
-            return Err(BoardError::InvalidPosition);
-        }
-        let pos_bit = BITS[pos];
-        if self.is_legal_move(pos) {
-            self.reverse(pos_bit);
-            swap(&mut self.player_board, &mut self.opponent_board);
-            self.turn = self.turn.opposite();
-            self.legal_moves_cache = None;
-        } else {
-            return Err(BoardError::InvalidMove);
-        }
-        Ok(())
+        self.do_move_undoable(pos).map(|_| ())
     }
 
     /// Pass the turn
@@ -489,13 +761,107 @@ impl Board {
     /// * If there is a legal move, return Err(BoardError::InvalidPass)
     /// * If the game is over, return Err(BoardError::InvalidPass)
     pub fn do_pass(&mut self) -> Result<(), BoardError> {
+        self.do_pass_undoable().map(|_| ())
+    }
+
+    /// Place the stones or pass in order
+    /// # Arguments
+    /// * `moves` - List of moves, None for a pass
+    /// # Returns
+    /// * `Result<(), BoardError>` - Ok(()) if successful, Err(BoardError) otherwise
+    /// # Note
+    /// * Errors are same as `do_move` and `do_pass`
+    /// * If a move fails, the moves before it stay played
+    /// * Use `play_transcript` to insert the passes automatically
+    pub fn do_moves(&mut self, moves: &[Option<usize>]) -> Result<(), BoardError> {
+        for &mv in moves {
+            match mv {
+                Some(pos) => self.do_move(pos)?,
+                None => self.do_pass()?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Place the stone and keep the information to undo it
+    /// # Arguments
+    /// * `pos` - Position to place the stone
+    /// # Returns
+    /// * `Result<UndoRecord, BoardError>` - UndoRecord if successful, Err(BoardError) otherwise
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::Board;
+    /// let mut board = Board::new();
+    /// let record = board.do_move_undoable(19).unwrap();
+    /// assert_eq!(record.get_flipped().count_ones(), 1);
+    /// board.undo_move(&record);
+    /// assert_eq!(board.get_board(), Board::new().get_board());
+    /// ```
+    /// # Note
+    /// * Errors are same as `do_move`
+    pub fn do_move_undoable(&mut self, pos: usize) -> Result<UndoRecord, BoardError> {
+        if pos >= BOARD_SIZE * BOARD_SIZE {
+            return Err(BoardError::InvalidPosition);
+        }
+        if !self.is_legal_move(pos) {
+            return Err(BoardError::InvalidMove);
+        }
+        let flipped = self.apply_move(BITS[pos]);
+        Ok(UndoRecord {
+            pos: Some(pos),
+            flipped,
+        })
+    }
+
+    // Place the stone without checking the legality and return the flipped stones
+    #[inline]
+    fn apply_move(&mut self, pos_bit: u64) -> u64 {
+        let flipped = self.get_flips_bit(pos_bit);
+        self.player_board ^= flipped | pos_bit;
+        self.opponent_board ^= flipped;
+        self.hash ^= zobrist::move_diff(pos_bit, flipped, self.turn) ^ zobrist::TURN_KEY;
+        swap(&mut self.player_board, &mut self.opponent_board);
+        self.turn = self.turn.opposite();
+        self.legal_moves_cache = None;
+        flipped
+    }
+
+    /// Pass the turn and keep the information to undo it
+    /// # Returns
+    /// * `Result<UndoRecord, BoardError>` - UndoRecord if successful, Err(BoardError) otherwise
+    /// # Note
+    /// * Errors are same as `do_pass`
+    pub fn do_pass_undoable(&mut self) -> Result<UndoRecord, BoardError> {
         if !self.is_pass() || self.is_game_over() {
             return Err(BoardError::InvalidPass);
         }
         swap(&mut self.player_board, &mut self.opponent_board);
         self.turn = self.turn.opposite();
         self.legal_moves_cache = None;
-        Ok(())
+        self.hash ^= zobrist::TURN_KEY;
+        Ok(UndoRecord {
+            pos: None,
+            flipped: 0,
+        })
+    }
+
+    /// Undo a move or a pass in O(1)
+    /// # Arguments
+    /// * `record` - UndoRecord returned by `do_move_undoable` or `do_pass_undoable`
+    /// # Note
+    /// * The record must be the one of the last move played on this board
+    /// * Records must be undone in the reverse order they were created
+    /// * The record is not validated, undoing a wrong record corrupts the board
+    pub fn undo_move(&mut self, record: &UndoRecord) {
+        swap(&mut self.player_board, &mut self.opponent_board);
+        self.turn = self.turn.opposite();
+        if let Some(pos) = record.pos {
+            self.player_board ^= record.flipped | BITS[pos];
+            self.opponent_board ^= record.flipped;
+            self.hash ^= zobrist::move_diff(BITS[pos], record.flipped, self.turn);
+        }
+        self.hash ^= zobrist::TURN_KEY;
+        self.legal_moves_cache = None;
     }
 
     #[inline]
@@ -538,6 +904,7 @@ impl Board {
                 opponent_board: self.player_board,
                 turn: self.turn.opposite(),
                 legal_moves_cache: None,
+                hash: self.hash ^ zobrist::TURN_KEY,
             };
             if opponent_board.is_pass() {
                 return true;
@@ -627,51 +994,38 @@ impl Board {
     /// * `Result<usize, BoardError>`
     /// # Note
     /// * If there is no legal move, return Err(BoardError::NoLegalMove)
+    /// * Use `get_random_move_with_rng` for reproducible moves
     pub fn get_random_move(&mut self) -> Result<usize, BoardError> {
-        let legal_moves_vec = self.get_legal_moves_vec();
-        if legal_moves_vec.is_empty() {
-            return Err(BoardError::NoLegalMove);
-        }
-        let random_index = rand::random::<usize>() % legal_moves_vec.len();
-        Ok(legal_moves_vec[random_index])
+        self.get_random_move_with_rng(&mut rand::thread_rng())
     }
 
-    /// Convert the board state to a string
+    /// Get random move using the given random number generator
+    /// # Arguments
+    /// * `rng` - Random number generator
     /// # Returns
-    /// * String representation of the board
+    /// * `Result<usize, BoardError>`
+    /// # Example
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use rust_reversi_core::board::Board;
+    /// let mut board = Board::new();
+    /// let m1 = board.get_random_move_with_rng(&mut StdRng::seed_from_u64(42)).unwrap();
+    /// let m2 = board.get_random_move_with_rng(&mut StdRng::seed_from_u64(42)).unwrap();
+    /// assert_eq!(m1, m2);
+    /// ```
     /// # Note
-    /// * X: Black, O: White
-    /// * this is used for fmt::Display
-    pub fn to_string(&self) -> Result<String, BoardError> {
-        let mut board_str = String::new();
-        let player_char = match self.turn {
-            Turn::Black => LINE_CHAR_BLACK,
-            Turn::White => LINE_CHAR_WHITE,
-        };
-        let opponent_char = match self.turn {
-            Turn::Black => LINE_CHAR_WHITE,
-            Turn::White => LINE_CHAR_BLACK,
-        };
-        board_str.push_str(" |abcdefgh\n-+--------\n");
-        for i in 0..BOARD_SIZE {
-            board_str.push_str(&format!("{}|", i + 1));
-            for j in 0..BOARD_SIZE {
-                let pos = BITS[i * BOARD_SIZE + j];
-                match (self.player_board & pos, self.opponent_board & pos) {
-                    (0, 0) => board_str.push(LINE_CHAR_EMPTY),
-                    (_, 0) => board_str.push(player_char),
-                    (0, _) => board_str.push(opponent_char),
-                    (_, _) => return Err(BoardError::InvalidState),
-                }
-            }
-            board_str.push('\n');
+    /// * If there is no legal move, return Err(BoardError::NoLegalMove)
+    /// * The same sequence of random numbers gives the same move
+    pub fn get_random_move_with_rng<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+    ) -> Result<usize, BoardError> {
+        let legal_moves_vec = self.get_legal_moves_vec();
+        if legal_moves_vec.is_empty() {
+            return Err(BoardError::NoLegalMove);
         }
-        Ok(board_str)
-    }
-}
-
-impl fmt::Display for Board {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.to_string().unwrap())
+        let random_index = rng.gen_range(0..legal_moves_vec.len());
+        Ok(legal_moves_vec[random_index])
     }
 }
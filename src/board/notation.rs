@@ -0,0 +1,228 @@
+use crate::board::{Board, BoardError, BOARD_SIZE};
+
+const COLUMN_CHARS: [char; BOARD_SIZE] = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
+const ROW_CHARS: [char; BOARD_SIZE] = ['1', '2', '3', '4', '5', '6', '7', '8'];
+pub(crate) const PASS_STR: &str = "PA";
+const PASS_STR_LONG: &str = "pass";
+
+/// Parse a square in algebraic notation
+/// # Arguments
+/// * `s` - Square such as "f5" (case insensitive)
+/// # Returns
+/// * `Result<usize, BoardError>` - Position of the square (0..64)
+/// # Example
+/// ```
+/// use rust_reversi_core::board::parse_square;
+/// assert_eq!(parse_square("a1").unwrap(), 0);
+/// assert_eq!(parse_square("F5").unwrap(), 37);
+/// assert!(parse_square("i9").is_err());
+/// ```
+/// # Note
+/// * Column is a..h from left to right, row is 1..8 from top to bottom
+/// * If the string is malformed, return Err(BoardError::InvalidNotation)
+pub fn parse_square(s: &str) -> Result<usize, BoardError> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(c), Some(r), None) => square_from_chars(c, r),
+        _ => Err(BoardError::InvalidNotation),
+    }
+}
+
+/// Format a position as a square in algebraic notation
+/// # Arguments
+/// * `pos` - Position of the square (0..64)
+/// # Returns
+/// * `Result<String, BoardError>` - Square such as "f5"
+/// # Example
+/// ```
+/// use rust_reversi_core::board::format_square;
+/// assert_eq!(format_square(37).unwrap(), "f5");
+/// ```
+/// # Note
+/// * If the position is out of range, return Err(BoardError::InvalidPosition)
+pub fn format_square(pos: usize) -> Result<String, BoardError> {
+    if pos >= BOARD_SIZE * BOARD_SIZE {
+        return Err(BoardError::InvalidPosition);
+    }
+    let (col, row) = square_to_chars(pos);
+    let mut square = String::with_capacity(2);
+    square.push(col);
+    square.push(row);
+    Ok(square)
+}
+
+/// Parse a move in algebraic notation
+/// # Arguments
+/// * `s` - Move such as "f5", or "PA" / "pass" for a pass (case insensitive)
+/// # Returns
+/// * `Result<Option<usize>, BoardError>` - Some(position) for a move, None for a pass
+/// # Example
+/// ```
+/// use rust_reversi_core::board::parse_move;
+/// assert_eq!(parse_move("d3").unwrap(), Some(19));
+/// assert_eq!(parse_move("PA").unwrap(), None);
+/// ```
+pub fn parse_move(s: &str) -> Result<Option<usize>, BoardError> {
+    let s = s.trim();
+    if s.eq_ignore_ascii_case(PASS_STR) || s.eq_ignore_ascii_case(PASS_STR_LONG) {
+        return Ok(None);
+    }
+    parse_square(s).map(Some)
+}
+
+/// Format a move in algebraic notation
+/// # Arguments
+/// * `mv` - Some(position) for a move, None for a pass
+/// # Returns
+/// * `Result<String, BoardError>` - Move such as "f5", or "PA" for a pass
+pub fn format_move(mv: Option<usize>) -> Result<String, BoardError> {
+    match mv {
+        Some(pos) => format_square(pos),
+        None => Ok(PASS_STR.to_string()),
+    }
+}
+
+/// Parse a transcript of moves
+/// # Arguments
+/// * `transcript` - Moves such as "f5d6c3d3c4"
+/// # Returns
+/// * `Result<Vec<Option<usize>>, BoardError>` - List of moves, None for a pass
+/// # Example
+/// ```
+/// use rust_reversi_core::board::parse_transcript;
+/// let moves = parse_transcript("f5d6 c3PA").unwrap();
+/// assert_eq!(moves, vec![Some(37), Some(43), Some(18), None]);
+/// ```
+/// # Note
+/// * Moves may be separated by whitespace
+/// * A pass is written as "PA" or "pass" (case insensitive)
+/// * Moves are not checked for legality, use `Board::play_transcript` for that
+pub fn parse_transcript(transcript: &str) -> Result<Vec<Option<usize>>, BoardError> {
+    let chars: Vec<char> = transcript.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut moves = Vec::with_capacity(chars.len() / 2);
+    let mut i = 0;
+    while i < chars.len() {
+        if i + 1 >= chars.len() {
+            return Err(BoardError::InvalidNotation);
+        }
+        if chars[i].eq_ignore_ascii_case(&'p') && chars[i + 1].eq_ignore_ascii_case(&'a') {
+            let is_long = chars.len() >= i + 4
+                && chars[i + 2].eq_ignore_ascii_case(&'s')
+                && chars[i + 3].eq_ignore_ascii_case(&'s');
+            i += if is_long { 4 } else { 2 };
+            moves.push(None);
+            continue;
+        }
+        moves.push(Some(square_from_chars(chars[i], chars[i + 1])?));
+        i += 2;
+    }
+    Ok(moves)
+}
+
+/// Format a list of moves as a transcript
+/// # Arguments
+/// * `moves` - List of moves, None for a pass
+/// # Returns
+/// * `Result<String, BoardError>` - Transcript such as "f5d6c3d3c4"
+/// # Note
+/// * Passes are written as "PA"
+/// * If a position is out of range, return Err(BoardError::InvalidPosition)
+/// * `Board` only holds the current position and does not record the moves played on it,
+///   so the moves come from `Board::play_transcript`, or a `GameHistory` is used
+///   to keep the moves (`GameHistory::from_transcript` and `GameHistory::get_transcript`)
+pub fn format_transcript(moves: &[Option<usize>]) -> Result<String, BoardError> {
+    let mut transcript = String::with_capacity(moves.len() * 2);
+    for &mv in moves {
+        transcript.push_str(&format_move(mv)?);
+    }
+    Ok(transcript)
+}
+
+// The position must be in range
+pub(crate) fn square_to_chars(pos: usize) -> (char, char) {
+    (COLUMN_CHARS[pos % BOARD_SIZE], ROW_CHARS[pos / BOARD_SIZE])
+}
+
+fn square_from_chars(c: char, r: char) -> Result<usize, BoardError> {
+    let col = COLUMN_CHARS
+        .iter()
+        .position(|&x| x == c.to_ascii_lowercase())
+        .ok_or(BoardError::InvalidNotation)?;
+    let row = ROW_CHARS
+        .iter()
+        .position(|&x| x == r)
+        .ok_or(BoardError::InvalidNotation)?;
+    Ok(row * BOARD_SIZE + col)
+}
+
+impl Board {
+    /// Create a board by playing a transcript from the initial position
+    /// # Arguments
+    /// * `transcript` - Moves such as "f5d6c3d3c4"
+    /// # Returns
+    /// * `Result<Board, BoardError>` - Board after all moves are played
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::{Board, Turn};
+    /// let board = Board::from_transcript("f5d6c3d3c4").unwrap();
+    /// assert_eq!(board.get_turn(), Turn::White);
+    /// assert_eq!(board.piece_sum(), 9);
+    /// ```
+    pub fn from_transcript(transcript: &str) -> Result<Board, BoardError> {
+        let mut board = Board::new();
+        board.play_transcript(transcript)?;
+        Ok(board)
+    }
+
+    /// Place the stone at a square given in algebraic notation
+    /// # Arguments
+    /// * `mv` - Move such as "f5", or "PA" / "pass" for a pass
+    /// # Returns
+    /// * `Result<(), BoardError>` - Ok(()) if successful, Err(BoardError) otherwise
+    /// # Note
+    /// * If the string is malformed, return Err(BoardError::InvalidNotation)
+    /// * Otherwise, same as `do_move` and `do_pass`
+    pub fn do_move_str(&mut self, mv: &str) -> Result<(), BoardError> {
+        match parse_move(mv)? {
+            Some(pos) => self.do_move(pos),
+            None => self.do_pass(),
+        }
+    }
+
+    /// Play a transcript of moves on the board
+    /// # Arguments
+    /// * `transcript` - Moves such as "f5d6c3d3c4"
+    /// # Returns
+    /// * `Result<Vec<Option<usize>>, BoardError>` - Moves actually played, None for a pass
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::{format_transcript, Board};
+    /// let mut board = Board::new();
+    /// let moves = board.play_transcript("F5D6C3").unwrap();
+    /// assert_eq!(format_transcript(&moves).unwrap(), "f5d6c3");
+    /// ```
+    /// # Note
+    /// * Passes may be omitted, they are inserted when the player has no legal move
+    /// * The returned moves include inserted passes
+    /// * The board is left unchanged if any move is malformed or illegal
+    pub fn play_transcript(&mut self, transcript: &str) -> Result<Vec<Option<usize>>, BoardError> {
+        let moves = parse_transcript(transcript)?;
+        let mut board = self.clone();
+        let mut played = Vec::with_capacity(moves.len());
+        for mv in moves {
+            match mv {
+                Some(pos) => {
+                    if board.is_pass() && !board.is_game_over() {
+                        board.do_pass()?;
+                        played.push(None);
+                    }
+                    board.do_move(pos)?;
+                }
+                None => board.do_pass()?,
+            }
+            played.push(mv);
+        }
+        *self = board;
+        Ok(played)
+    }
+}
@@ -0,0 +1,97 @@
+use crate::board::{Board, BITS};
+
+/// How a pass is counted by perft
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum PerftMode {
+    /// A pass consumes one ply
+    PassIsPly,
+    /// A pass does not consume a ply
+    PassIsFree,
+}
+
+fn perft_inner(board: &mut Board, depth: usize, mode: PerftMode) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    let mut legal_moves = board.get_legal_moves();
+    if legal_moves == 0 {
+        if board.is_game_over() {
+            return 1;
+        }
+        let record = board.do_pass_undoable().unwrap();
+        let count = match mode {
+            PerftMode::PassIsPly => perft_inner(board, depth - 1, mode),
+            PerftMode::PassIsFree => perft_inner(board, depth, mode),
+        };
+        board.undo_move(&record);
+        return count;
+    }
+    if depth == 1 {
+        return legal_moves.count_ones() as u64;
+    }
+    let mut count = 0;
+    while legal_moves != 0 {
+        let pos = legal_moves.leading_zeros() as usize;
+        legal_moves ^= BITS[pos];
+        let record = board.do_move_undoable(pos).unwrap();
+        count += perft_inner(board, depth - 1, mode);
+        board.undo_move(&record);
+    }
+    count
+}
+
+impl Board {
+    /// Count the leaf nodes of the game tree
+    /// # Arguments
+    /// * `depth` - Depth of the game tree
+    /// * `mode` - How a pass is counted
+    /// # Returns
+    /// * Number of leaf nodes
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::{Board, PerftMode};
+    /// let board = Board::new();
+    /// assert_eq!(board.perft(4, PerftMode::PassIsPly), 244);
+    /// ```
+    /// # Note
+    /// * A position where the game is over is counted as a leaf
+    /// * Used to verify the move generation
+    pub fn perft(&self, depth: usize, mode: PerftMode) -> u64 {
+        let mut board = self.clone();
+        perft_inner(&mut board, depth, mode)
+    }
+
+    /// Count the leaf nodes of the game tree for each move
+    /// # Arguments
+    /// * `depth` - Depth of the game tree
+    /// * `mode` - How a pass is counted
+    /// # Returns
+    /// * List of (move, number of leaf nodes), None for a pass
+    /// # Note
+    /// * The sum of the counts is equal to `perft` unless depth is 0 or the game is over
+    /// * If depth is 0 or the game is over, return an empty list
+    pub fn perft_divide(&self, depth: usize, mode: PerftMode) -> Vec<(Option<usize>, u64)> {
+        let mut board = self.clone();
+        if depth == 0 || board.is_game_over() {
+            return Vec::new();
+        }
+        if board.is_pass() {
+            board.do_pass().unwrap();
+            let count = match mode {
+                PerftMode::PassIsPly => perft_inner(&mut board, depth - 1, mode),
+                PerftMode::PassIsFree => perft_inner(&mut board, depth, mode),
+            };
+            return vec![(None, count)];
+        }
+        board
+            .get_legal_moves_vec()
+            .iter()
+            .map(|&pos| {
+                let record = board.do_move_undoable(pos).unwrap();
+                let count = perft_inner(&mut board, depth - 1, mode);
+                board.undo_move(&record);
+                (Some(pos), count)
+            })
+            .collect()
+    }
+}
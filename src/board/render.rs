@@ -0,0 +1,335 @@
+use crate::board::{
+    Board, BoardError, Square, Turn, BITS, BOARD_SIZE, LINE_CHAR_BLACK, LINE_CHAR_EMPTY,
+    LINE_CHAR_WHITE,
+};
+use core::fmt;
+use std::str::FromStr;
+
+const CHAR_LAST_BLACK: char = 'x';
+const CHAR_LAST_WHITE: char = 'o';
+const CHAR_HINT: char = '*';
+const CHAR_INVALID: char = '#';
+const HEADER: &str = " |abcdefgh";
+const SEPARATOR: &str = "-+--------";
+const TURN_SUFFIX: &str = " to move";
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_BLACK: &str = "\x1b[30;42m";
+const ANSI_WHITE: &str = "\x1b[97;42m";
+const ANSI_EMPTY: &str = "\x1b[32;42m";
+const ANSI_HINT: &str = "\x1b[33;42m";
+const ANSI_LAST_MOVE: &str = "\x1b[1;41m";
+
+/// Options for `Board::render`
+/// # Example
+/// ```
+/// use rust_reversi_core::board::{Board, RenderOptions};
+/// let board = Board::from_transcript("f5").unwrap();
+/// let mut options = RenderOptions::new();
+/// options.set_legal_moves(true);
+/// options.set_last_move("f5".parse().ok());
+/// options.set_disc_count(true);
+/// assert_eq!(board.render(&options), format!(
+///     "{}{}{}{}{}{}{}{}{}{}{}",
+///     " |abcdefgh\n",
+///     "-+--------\n",
+///     "1|--------\n",
+///     "2|--------\n",
+///     "3|--------\n",
+///     "4|---OX*--\n",
+///     "5|---XXx--\n",
+///     "6|---*-*--\n",
+///     "7|--------\n",
+///     "8|--------\n",
+///     "X: 4 O: 1\n",
+/// ));
+/// ```
+/// # Note
+/// * All options are off by default, which renders only the grid
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct RenderOptions {
+    legal_moves: bool,
+    last_move: Option<Square>,
+    disc_count: bool,
+    turn: bool,
+    ansi: bool,
+}
+
+impl RenderOptions {
+    /// Create a new RenderOptions instance with all options off
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get if the legal moves of the side to move are marked
+    pub fn get_legal_moves(&self) -> bool {
+        self.legal_moves
+    }
+
+    /// Set if the legal moves of the side to move are marked with '*'
+    pub fn set_legal_moves(&mut self, legal_moves: bool) {
+        self.legal_moves = legal_moves;
+    }
+
+    /// Get the square marked as the last move
+    pub fn get_last_move(&self) -> Option<Square> {
+        self.last_move
+    }
+
+    /// Set the square marked as the last move
+    /// # Note
+    /// * The stone on the square is written in lowercase ('x' or 'o')
+    /// * Nothing is marked if the square is empty
+    pub fn set_last_move(&mut self, last_move: Option<Square>) {
+        self.last_move = last_move;
+    }
+
+    /// Get if the number of stones is shown
+    pub fn get_disc_count(&self) -> bool {
+        self.disc_count
+    }
+
+    /// Set if the number of stones is shown as "X: 2 O: 2" below the grid
+    pub fn set_disc_count(&mut self, disc_count: bool) {
+        self.disc_count = disc_count;
+    }
+
+    /// Get if the side to move is shown
+    pub fn get_turn(&self) -> bool {
+        self.turn
+    }
+
+    /// Set if the side to move is shown as "X to move" below the grid
+    pub fn set_turn(&mut self, turn: bool) {
+        self.turn = turn;
+    }
+
+    /// Get if ANSI escape codes are used
+    pub fn get_ansi(&self) -> bool {
+        self.ansi
+    }
+
+    /// Set if ANSI escape codes are used to color the grid for terminals
+    pub fn set_ansi(&mut self, ansi: bool) {
+        self.ansi = ansi;
+    }
+}
+
+fn turn_char(turn: Turn) -> char {
+    match turn {
+        Turn::Black => LINE_CHAR_BLACK,
+        Turn::White => LINE_CHAR_WHITE,
+    }
+}
+
+impl Board {
+    /// Render the board as a grid
+    /// # Arguments
+    /// * `options` - What to show in addition to the stones
+    /// # Returns
+    /// * Grid with a header, such as "4|---OX---" for each row
+    /// # Note
+    /// * X: Black, O: White, -: Empty
+    /// * A square with both player's and opponent's stones is written as '#'
+    /// * The result without ANSI escape codes can be read back by `Board::from_str`
+    pub fn render(&self, options: &RenderOptions) -> String {
+        let (black_board, white_board) = match self.turn {
+            Turn::Black => (self.player_board, self.opponent_board),
+            Turn::White => (self.opponent_board, self.player_board),
+        };
+        let hints = if options.legal_moves {
+            self.legal_moves_cache
+                .unwrap_or_else(|| self.get_legal_moves_scalar())
+        } else {
+            0
+        };
+        let last_move = options.last_move.map_or(0, |square| square.get_bit());
+
+        let mut s = String::new();
+        s.push_str(HEADER);
+        s.push('\n');
+        s.push_str(SEPARATOR);
+        s.push('\n');
+        for row in 0..BOARD_SIZE {
+            s.push_str(&format!("{}|", row + 1));
+            for col in 0..BOARD_SIZE {
+                let bit = BITS[row * BOARD_SIZE + col];
+                let is_last = last_move & bit != 0;
+                let (c, style) = match (black_board & bit, white_board & bit) {
+                    (0, 0) if hints & bit != 0 => (CHAR_HINT, ANSI_HINT),
+                    (0, 0) => (LINE_CHAR_EMPTY, ANSI_EMPTY),
+                    (_, 0) if is_last => (CHAR_LAST_BLACK, ANSI_LAST_MOVE),
+                    (_, 0) => (LINE_CHAR_BLACK, ANSI_BLACK),
+                    (0, _) if is_last => (CHAR_LAST_WHITE, ANSI_LAST_MOVE),
+                    (0, _) => (LINE_CHAR_WHITE, ANSI_WHITE),
+                    (_, _) => (CHAR_INVALID, ANSI_LAST_MOVE),
+                };
+                if options.ansi {
+                    s.push_str(style);
+                }
+                s.push(c);
+            }
+            if options.ansi {
+                s.push_str(ANSI_RESET);
+            }
+            s.push('\n');
+        }
+        if options.disc_count {
+            s.push_str(&format!(
+                "{}: {} {}: {}\n",
+                LINE_CHAR_BLACK,
+                black_board.count_ones(),
+                LINE_CHAR_WHITE,
+                white_board.count_ones()
+            ));
+        }
+        if options.turn {
+            s.push(turn_char(self.turn));
+            s.push_str(TURN_SUFFIX);
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl fmt::Display for Board {
+    /// Same as `render` with all options off, which shows only the grid
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render(&RenderOptions::new()))
+    }
+}
+
+// remove ANSI escape sequences such as "\x1b[30;42m"
+fn strip_ansi(s: &str) -> String {
+    let mut stripped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
+fn parse_turn(s: &str) -> Result<Turn, BoardError> {
+    match s {
+        "X" => Ok(Turn::Black),
+        "O" => Ok(Turn::White),
+        _ => Err(BoardError::InvalidFormat),
+    }
+}
+
+// add the stones of one square to (black, white)
+fn parse_square_char(c: char, bit: u64, boards: &mut (u64, u64)) -> Result<(), BoardError> {
+    match c {
+        LINE_CHAR_BLACK | CHAR_LAST_BLACK => boards.0 |= bit,
+        LINE_CHAR_WHITE | CHAR_LAST_WHITE => boards.1 |= bit,
+        LINE_CHAR_EMPTY | CHAR_HINT => {}
+        _ => return Err(BoardError::InvalidCharactor),
+    }
+    Ok(())
+}
+
+fn parse_line(line: &str) -> Result<(u64, u64, Turn), BoardError> {
+    let chars: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    let turn = match chars.len() {
+        n if n == BOARD_SIZE * BOARD_SIZE => Turn::Black,
+        n if n == BOARD_SIZE * BOARD_SIZE + 1 => {
+            parse_turn(&chars[BOARD_SIZE * BOARD_SIZE].to_string())?
+        }
+        _ => return Err(BoardError::InvalidFormat),
+    };
+    let mut boards = (0, 0);
+    for (i, &c) in chars[..BOARD_SIZE * BOARD_SIZE].iter().enumerate() {
+        parse_square_char(c, BITS[i], &mut boards)?;
+    }
+    Ok((boards.0, boards.1, turn))
+}
+
+fn parse_grid(lines: &[&str]) -> Result<(u64, u64, Turn), BoardError> {
+    let mut boards = (0, 0);
+    let mut rows = 0;
+    let mut turn = Turn::Black;
+    for line in lines {
+        if *line == HEADER.trim() || *line == SEPARATOR {
+            continue;
+        }
+        if let Some(t) = line.strip_suffix(TURN_SUFFIX) {
+            turn = parse_turn(t)?;
+            continue;
+        }
+        if line.starts_with(&format!("{}:", LINE_CHAR_BLACK)) {
+            // disc count, which is derived from the grid
+            continue;
+        }
+        let (label, squares) = line.split_once('|').ok_or(BoardError::InvalidFormat)?;
+        if rows >= BOARD_SIZE || label != (rows + 1).to_string() {
+            return Err(BoardError::InvalidFormat);
+        }
+        let squares: Vec<char> = squares.chars().collect();
+        if squares.len() != BOARD_SIZE {
+            return Err(BoardError::InvalidFormat);
+        }
+        for (col, &c) in squares.iter().enumerate() {
+            parse_square_char(c, BITS[rows * BOARD_SIZE + col], &mut boards)?;
+        }
+        rows += 1;
+    }
+    if rows != BOARD_SIZE {
+        return Err(BoardError::InvalidFormat);
+    }
+    Ok((boards.0, boards.1, turn))
+}
+
+impl FromStr for Board {
+    type Err = BoardError;
+
+    /// Parse a board from the line format or the grid format
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::{Board, RenderOptions, Turn};
+    /// let board = Board::from_transcript("f5d6c3").unwrap();
+    /// let mut options = RenderOptions::new();
+    /// options.set_turn(true);
+    /// let parsed: Board = board.render(&options).parse().unwrap();
+    /// assert!(parsed == board);
+    /// assert_eq!(board.get_turn(), Turn::White);
+    ///
+    /// let line = board.get_board_line().unwrap();
+    /// let parsed: Board = format!("{} O", line).parse().unwrap();
+    /// assert!(parsed == board);
+    /// ```
+    /// # Note
+    /// * The line format is 64 squares as `get_board_line`, optionally followed by 'X' or 'O'
+    ///   for the side to move
+    /// * The grid format is the output of `render` and `Display` without ANSI escape codes,
+    ///   but ANSI escape codes are ignored anyway
+    /// * 'x' and 'o' are read as stones, '*' as an empty square
+    /// * If the side to move is not given, Black is to move
+    /// * If a square character is invalid, return Err(BoardError::InvalidCharactor)
+    /// * If the layout is malformed, return Err(BoardError::InvalidFormat)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = strip_ansi(s);
+        let lines: Vec<&str> = s
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect();
+        let (black_board, white_board, turn) = match lines.len() {
+            1 => parse_line(lines[0])?,
+            _ => parse_grid(&lines)?,
+        };
+        let mut board = Board::new();
+        match turn {
+            Turn::Black => board.set_board(black_board, white_board, Turn::Black),
+            Turn::White => board.set_board(white_board, black_board, Turn::White),
+        }
+        Ok(board)
+    }
+}
@@ -0,0 +1,153 @@
+// AVX2 move generation
+// The four directions (1, 8, 9, 7) are processed in the four 64-bit lanes at once,
+// with the same masks as the scalar implementation in `Board::get_legal_moves_scalar`
+// and `Board::get_flips_bit_scalar`.
+
+/// Check if the SIMD move generation is used
+/// # Returns
+/// * true if the crate is built with the `simd` feature and the CPU supports AVX2
+/// # Note
+/// * The CPU is checked at runtime, so the same binary runs on CPUs without AVX2
+/// * The results of the SIMD and the scalar move generation are always the same
+pub fn is_simd_enabled() -> bool {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    {
+        std::arch::is_x86_feature_detected!("avx2")
+    }
+    #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+    {
+        false
+    }
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+pub(crate) mod avx2 {
+    use std::arch::x86_64::*;
+
+    // lanes are (1, 8, 9, 7) from lane 0
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn shifts() -> __m256i {
+        _mm256_set_epi64x(7, 9, 8, 1)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn or_lanes(x: __m256i) -> u64 {
+        let x = _mm_or_si128(_mm256_castsi256_si128(x), _mm256_extracti128_si256(x, 1));
+        let x = _mm_or_si128(x, _mm_unpackhi_epi64(x, x));
+        _mm_cvtsi128_si64(x) as u64
+    }
+
+    /// # Safety
+    /// * The CPU must support AVX2
+    #[target_feature(enable = "avx2")]
+    pub(crate) unsafe fn get_legal_moves(player_board: u64, opponent_board: u64) -> u64 {
+        let shift1 = shifts();
+        let shift2 = _mm256_add_epi64(shift1, shift1);
+        let player = _mm256_set1_epi64x(player_board as i64);
+        let watch = _mm256_and_si256(
+            _mm256_set1_epi64x(opponent_board as i64),
+            _mm256_set_epi64x(
+                0x7E_7E_7E_7E_7E_7E_7E_7E,
+                0x7E_7E_7E_7E_7E_7E_7E_7E,
+                -1,
+                0x7E_7E_7E_7E_7E_7E_7E_7E,
+            ),
+        );
+        let mut flip_l = _mm256_and_si256(watch, _mm256_sllv_epi64(player, shift1));
+        let mut flip_r = _mm256_and_si256(watch, _mm256_srlv_epi64(player, shift1));
+        flip_l = _mm256_or_si256(
+            flip_l,
+            _mm256_and_si256(watch, _mm256_sllv_epi64(flip_l, shift1)),
+        );
+        flip_r = _mm256_or_si256(
+            flip_r,
+            _mm256_and_si256(watch, _mm256_srlv_epi64(flip_r, shift1)),
+        );
+        let watch_l = _mm256_and_si256(watch, _mm256_sllv_epi64(watch, shift1));
+        let watch_r = _mm256_and_si256(watch, _mm256_srlv_epi64(watch, shift1));
+        for _ in 0..2 {
+            flip_l = _mm256_or_si256(
+                flip_l,
+                _mm256_and_si256(watch_l, _mm256_sllv_epi64(flip_l, shift2)),
+            );
+            flip_r = _mm256_or_si256(
+                flip_r,
+                _mm256_and_si256(watch_r, _mm256_srlv_epi64(flip_r, shift2)),
+            );
+        }
+        let legal_moves = _mm256_or_si256(
+            _mm256_sllv_epi64(flip_l, shift1),
+            _mm256_srlv_epi64(flip_r, shift1),
+        );
+        or_lanes(legal_moves) & !(player_board | opponent_board)
+    }
+
+    /// # Safety
+    /// * The CPU must support AVX2
+    #[target_feature(enable = "avx2")]
+    pub(crate) unsafe fn get_flips(player_board: u64, opponent_board: u64, pos: u64) -> u64 {
+        let shift1 = shifts();
+        let shift2 = _mm256_add_epi64(shift1, shift1);
+        let player = _mm256_set1_epi64x(player_board as i64);
+        let opponent = _mm256_set1_epi64x(opponent_board as i64);
+        let pos = _mm256_set1_epi64x(pos as i64);
+        let zero = _mm256_setzero_si256();
+
+        // left: upper left, upper right, up, left
+        let mask_l = _mm256_set_epi64x(
+            0x7F_7F_7F_7F_7F_7F_7F_00,
+            0xFE_FE_FE_FE_FE_FE_FE_00u64 as i64,
+            0xFF_FF_FF_FF_FF_FF_FF_00u64 as i64,
+            0xFE_FE_FE_FE_FE_FE_FE_FEu64 as i64,
+        );
+        let watch_l = _mm256_and_si256(opponent, mask_l);
+        let watch_l2 = _mm256_and_si256(watch_l, _mm256_sllv_epi64(watch_l, shift1));
+        let mut flip_l = _mm256_and_si256(watch_l, _mm256_sllv_epi64(pos, shift1));
+        flip_l = _mm256_or_si256(
+            flip_l,
+            _mm256_and_si256(watch_l, _mm256_sllv_epi64(flip_l, shift1)),
+        );
+        for _ in 0..2 {
+            flip_l = _mm256_or_si256(
+                flip_l,
+                _mm256_and_si256(watch_l2, _mm256_sllv_epi64(flip_l, shift2)),
+            );
+        }
+        // the stones are reversed only if the player's stone is at the end
+        let outflank_l = _mm256_and_si256(
+            _mm256_and_si256(mask_l, player),
+            _mm256_sllv_epi64(flip_l, shift1),
+        );
+        flip_l = _mm256_andnot_si256(_mm256_cmpeq_epi64(outflank_l, zero), flip_l);
+
+        // right: lower left, lower right, down, right
+        let mask_r = _mm256_set_epi64x(
+            0x00_FE_FE_FE_FE_FE_FE_FE,
+            0x00_7F_7F_7F_7F_7F_7F_7F,
+            0x00_FF_FF_FF_FF_FF_FF_FF,
+            0x7F_7F_7F_7F_7F_7F_7F_7F,
+        );
+        let watch_r = _mm256_and_si256(opponent, mask_r);
+        let watch_r2 = _mm256_and_si256(watch_r, _mm256_srlv_epi64(watch_r, shift1));
+        let mut flip_r = _mm256_and_si256(watch_r, _mm256_srlv_epi64(pos, shift1));
+        flip_r = _mm256_or_si256(
+            flip_r,
+            _mm256_and_si256(watch_r, _mm256_srlv_epi64(flip_r, shift1)),
+        );
+        for _ in 0..2 {
+            flip_r = _mm256_or_si256(
+                flip_r,
+                _mm256_and_si256(watch_r2, _mm256_srlv_epi64(flip_r, shift2)),
+            );
+        }
+        let outflank_r = _mm256_and_si256(
+            _mm256_and_si256(mask_r, player),
+            _mm256_srlv_epi64(flip_r, shift1),
+        );
+        flip_r = _mm256_andnot_si256(_mm256_cmpeq_epi64(outflank_r, zero), flip_r);
+
+        or_lanes(_mm256_or_si256(flip_l, flip_r))
+    }
+}
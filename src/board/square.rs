@@ -0,0 +1,263 @@
+use crate::board::notation::{square_to_chars, PASS_STR};
+use crate::board::{parse_move, parse_square, Board, BoardError, UndoRecord, BOARD_SIZE};
+use core::fmt;
+use std::str::FromStr;
+
+/// Square on the board
+/// # Note
+/// * Squares are numbered from a1 (0) to h8 (63) row by row,
+///   same as the positions used by `Board::do_move`
+/// * A Square is always in range, so it can be used without checking
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "usize", into = "usize")
+)]
+pub struct Square(u8);
+
+impl Square {
+    /// Create a new Square instance from a position
+    /// # Arguments
+    /// * `pos` - Position of the square (0..64)
+    /// # Returns
+    /// * `Result<Square, BoardError>` - Square at the position
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::Square;
+    /// let square = Square::new(37).unwrap();
+    /// assert_eq!(square.to_string(), "f5");
+    /// assert!(Square::new(64).is_err());
+    /// ```
+    /// # Note
+    /// * If the position is out of range, return Err(BoardError::InvalidPosition)
+    pub fn new(pos: usize) -> Result<Square, BoardError> {
+        if pos >= BOARD_SIZE * BOARD_SIZE {
+            return Err(BoardError::InvalidPosition);
+        }
+        Ok(Square(pos as u8))
+    }
+
+    /// Create a new Square instance from a row and a column
+    /// # Arguments
+    /// * `row` - Row of the square (0..8), 0 is the row "1"
+    /// * `col` - Column of the square (0..8), 0 is the column "a"
+    /// # Returns
+    /// * `Result<Square, BoardError>` - Square at the row and the column
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::Square;
+    /// let square = Square::from_row_col(4, 5).unwrap();
+    /// assert_eq!(square.to_string(), "f5");
+    /// ```
+    /// # Note
+    /// * If the row or the column is out of range, return Err(BoardError::InvalidPosition)
+    pub fn from_row_col(row: usize, col: usize) -> Result<Square, BoardError> {
+        if row >= BOARD_SIZE || col >= BOARD_SIZE {
+            return Err(BoardError::InvalidPosition);
+        }
+        Ok(Square((row * BOARD_SIZE + col) as u8))
+    }
+
+    /// Create a new Square instance from a bitboard with a single bit
+    /// # Arguments
+    /// * `bit` - Bitboard with exactly one bit set
+    /// # Returns
+    /// * `Result<Square, BoardError>` - Square of the bit
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::Square;
+    /// let square = Square::from_bit(0x8000000000000000).unwrap();
+    /// assert_eq!(square.to_string(), "a1");
+    /// assert!(Square::from_bit(0).is_err());
+    /// ```
+    /// # Note
+    /// * If the number of set bits is not one, return Err(BoardError::InvalidPosition)
+    pub fn from_bit(bit: u64) -> Result<Square, BoardError> {
+        if bit.count_ones() != 1 {
+            return Err(BoardError::InvalidPosition);
+        }
+        Ok(Square(bit.leading_zeros() as u8))
+    }
+
+    /// Get the position of the square
+    /// # Returns
+    /// * Position of the square (0..64)
+    #[inline]
+    pub fn get_index(&self) -> usize {
+        self.0 as usize
+    }
+
+    /// Get the row of the square
+    /// # Returns
+    /// * Row of the square (0..8), 0 is the row "1"
+    #[inline]
+    pub fn get_row(&self) -> usize {
+        self.get_index() / BOARD_SIZE
+    }
+
+    /// Get the column of the square
+    /// # Returns
+    /// * Column of the square (0..8), 0 is the column "a"
+    #[inline]
+    pub fn get_col(&self) -> usize {
+        self.get_index() % BOARD_SIZE
+    }
+
+    /// Get the square as a bitboard
+    /// # Returns
+    /// * Bitboard with only the bit of the square set
+    #[inline]
+    pub fn get_bit(&self) -> u64 {
+        1 << (BOARD_SIZE * BOARD_SIZE - 1 - self.get_index())
+    }
+}
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (col, row) = square_to_chars(self.get_index());
+        write!(f, "{}{}", col, row)
+    }
+}
+
+impl FromStr for Square {
+    type Err = BoardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Square::new(parse_square(s)?)
+    }
+}
+
+impl TryFrom<usize> for Square {
+    type Error = BoardError;
+
+    fn try_from(pos: usize) -> Result<Self, Self::Error> {
+        Square::new(pos)
+    }
+}
+
+impl From<Square> for usize {
+    fn from(square: Square) -> Self {
+        square.get_index()
+    }
+}
+
+/// Move of a player
+/// # Note
+/// * Equivalent to the `Option<usize>` form used in transcripts,
+///   where `Move::Pass` is `None`
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Move {
+    Place(Square),
+    Pass,
+}
+
+impl Move {
+    /// Get the square of the move
+    /// # Returns
+    /// * Some(square) for a move, None for a pass
+    pub fn get_square(&self) -> Option<Square> {
+        match self {
+            Move::Place(square) => Some(*square),
+            Move::Pass => None,
+        }
+    }
+
+    /// Check if the move is a pass
+    pub fn is_pass(&self) -> bool {
+        matches!(self, Move::Pass)
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Move::Place(square) => write!(f, "{}", square),
+            Move::Pass => write!(f, "{}", PASS_STR),
+        }
+    }
+}
+
+impl FromStr for Move {
+    type Err = BoardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Move::try_from(parse_move(s)?)
+    }
+}
+
+impl From<Square> for Move {
+    fn from(square: Square) -> Self {
+        Move::Place(square)
+    }
+}
+
+impl TryFrom<Option<usize>> for Move {
+    type Error = BoardError;
+
+    fn try_from(mv: Option<usize>) -> Result<Self, Self::Error> {
+        match mv {
+            Some(pos) => Square::new(pos).map(Move::Place),
+            None => Ok(Move::Pass),
+        }
+    }
+}
+
+impl From<Move> for Option<usize> {
+    fn from(mv: Move) -> Self {
+        mv.get_square().map(|square| square.get_index())
+    }
+}
+
+impl Board {
+    /// Play a move on the board
+    /// # Arguments
+    /// * `mv` - Move to play
+    /// # Returns
+    /// * `Result<(), BoardError>` - Ok(()) if successful, Err(BoardError) otherwise
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::{Board, Move};
+    /// let mut board = Board::new();
+    /// board.play_move("f5".parse::<Move>().unwrap()).unwrap();
+    /// assert_eq!(board.piece_sum(), 5);
+    /// assert!(board.play_move(Move::Pass).is_err());
+    /// ```
+    /// # Note
+    /// * Errors are same as `do_move` and `do_pass`
+    pub fn play_move(&mut self, mv: Move) -> Result<(), BoardError> {
+        match mv {
+            Move::Place(square) => self.do_move(square.get_index()),
+            Move::Pass => self.do_pass(),
+        }
+    }
+
+    /// Play a move on the board and keep the information to undo it
+    /// # Arguments
+    /// * `mv` - Move to play
+    /// # Returns
+    /// * `Result<UndoRecord, BoardError>` - UndoRecord if successful, Err(BoardError) otherwise
+    /// # Note
+    /// * Errors are same as `do_move_undoable` and `do_pass_undoable`
+    pub fn play_move_undoable(&mut self, mv: Move) -> Result<UndoRecord, BoardError> {
+        match mv {
+            Move::Place(square) => self.do_move_undoable(square.get_index()),
+            Move::Pass => self.do_pass_undoable(),
+        }
+    }
+
+    /// Get if the square is a legal move
+    pub fn is_legal_square(&mut self, square: Square) -> bool {
+        self.get_legal_moves() & square.get_bit() != 0
+    }
+
+    /// Get the legal moves for the player as a vector of squares
+    /// # Note
+    /// * The squares are in ascending order from a1 to h8
+    pub fn get_legal_squares(&mut self) -> Vec<Square> {
+        self.get_legal_moves_iter()
+            .map(|pos| Square(pos as u8))
+            .collect()
+    }
+}
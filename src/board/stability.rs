@@ -0,0 +1,109 @@
+use crate::board::{Board, COL_A, COL_H, NOT_COL_A, NOT_COL_H};
+
+const BORDER: u64 = 0xFF_81_81_81_81_81_81_FF;
+const ROW_1: u64 = 0xFF_00_00_00_00_00_00_00;
+const ROW_8: u64 = 0x00_00_00_00_00_00_00_FF;
+
+// Masks of the 15 diagonals in each direction.
+// DIAGONALS[0]: a1-h8 direction, DIAGONALS[1]: h1-a8 direction
+const DIAGONALS: [[u64; 15]; 2] = {
+    let mut masks = [[0u64; 15]; 2];
+    let mut pos = 0;
+    while pos < 64 {
+        let (r, c) = (pos / 8, pos % 8);
+        let bit = 1u64 << (63 - pos);
+        masks[0][r + 7 - c] |= bit;
+        masks[1][r + c] |= bit;
+        pos += 1;
+    }
+    masks
+};
+
+// Get the squares whose line is full for each direction.
+// Returns (horizontal, vertical, a1-h8 diagonal, h1-a8 diagonal).
+fn get_full_lines(occupied: u64) -> (u64, u64, u64, u64) {
+    let mut horizontal = 0;
+    let mut vertical = 0;
+    for i in 0..8 {
+        let row = ROW_8 << (i * 8);
+        if occupied & row == row {
+            horizontal |= row;
+        }
+        let col = COL_H << i;
+        if occupied & col == col {
+            vertical |= col;
+        }
+    }
+    let mut diagonal = 0;
+    let mut anti_diagonal = 0;
+    for (&d, &a) in DIAGONALS[0].iter().zip(DIAGONALS[1].iter()) {
+        if occupied & d == d {
+            diagonal |= d;
+        }
+        if occupied & a == a {
+            anti_diagonal |= a;
+        }
+    }
+    (horizontal, vertical, diagonal, anti_diagonal)
+}
+
+// A stone is stable if, in each of the 4 directions, the line is full
+// or one of the neighbors is the border or a stable stone of the same color.
+fn get_stable_bits(stones: u64, full_lines: (u64, u64, u64, u64)) -> u64 {
+    let (full_h, full_v, full_d, full_a) = full_lines;
+    let edge_h = full_h | COL_A | COL_H;
+    let edge_v = full_v | ROW_1 | ROW_8;
+    let edge_d = full_d | BORDER;
+    let edge_a = full_a | BORDER;
+    let mut stable = 0;
+    loop {
+        let h = edge_h | ((stable >> 1) & NOT_COL_A) | ((stable << 1) & NOT_COL_H);
+        let v = edge_v | (stable >> 8) | (stable << 8);
+        let d = edge_d | ((stable >> 9) & NOT_COL_A) | ((stable << 9) & NOT_COL_H);
+        let a = edge_a | ((stable >> 7) & NOT_COL_H) | ((stable << 7) & NOT_COL_A);
+        let new_stable = stones & h & v & d & a;
+        if new_stable == stable {
+            return stable;
+        }
+        stable = new_stable;
+    }
+}
+
+impl Board {
+    /// Get the stable stones, which can never be reversed
+    /// # Returns
+    /// * Tuple of (player's stable stones, opponent's stable stones) as bitboards
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::{Board, Turn};
+    /// let mut board = Board::new();
+    /// board.set_board_str(
+    ///   format!(
+    ///     "{}{}{}{}{}{}{}{}",
+    ///     "XXXO----",
+    ///     "X-------",
+    ///     "--------",
+    ///     "---XO---",
+    ///     "---OX---",
+    ///     "--------",
+    ///     "--------",
+    ///     "--------",
+    ///   ).as_str(),
+    ///   Turn::Black,
+    /// ).unwrap();
+    /// let (player_stable, opponent_stable) = board.get_stable_stones();
+    /// assert_eq!(player_stable.count_ones(), 4); // a1, b1, c1, a2
+    /// assert_eq!(opponent_stable, 0);
+    /// ```
+    /// # Note
+    /// * Stones are stable if the line in each direction is full
+    ///   or protected by the border or other stable stones of the same color
+    /// * The result is a subset of all stable stones, but every returned stone is stable
+    pub fn get_stable_stones(&self) -> (u64, u64) {
+        let full_lines = get_full_lines(self.player_board | self.opponent_board);
+        (
+            get_stable_bits(self.player_board, full_lines),
+            get_stable_bits(self.opponent_board, full_lines),
+        )
+    }
+}
@@ -0,0 +1,170 @@
+use crate::board::{Board, BoardError, BOARD_SIZE};
+
+/// One of the 8 symmetries of the board
+/// # Note
+/// * Rotations are clockwise as the board is displayed by `to_string`
+/// * FlipVertical: a1 <-> a8, FlipHorizontal: a1 <-> h1
+/// * FlipDiagonal: mirror on the a1-h8 diagonal
+/// * FlipAntiDiagonal: mirror on the h1-a8 diagonal
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Transform {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipVertical,
+    FlipHorizontal,
+    FlipDiagonal,
+    FlipAntiDiagonal,
+}
+
+#[inline]
+fn flip_vertical(bits: u64) -> u64 {
+    bits.swap_bytes()
+}
+
+#[inline]
+fn flip_horizontal(bits: u64) -> u64 {
+    bits.reverse_bits().swap_bytes()
+}
+
+#[inline]
+fn flip_diagonal(bits: u64) -> u64 {
+    let mut bits = bits;
+    let t = 0x0F_0F_0F_0F_00_00_00_00 & (bits ^ (bits << 28));
+    bits ^= t ^ (t >> 28);
+    let t = 0x33_33_00_00_33_33_00_00 & (bits ^ (bits << 14));
+    bits ^= t ^ (t >> 14);
+    let t = 0x55_00_55_00_55_00_55_00 & (bits ^ (bits << 7));
+    bits ^= t ^ (t >> 7);
+    bits
+}
+
+impl Transform {
+    /// All transforms, starting with Identity
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipVertical,
+        Transform::FlipHorizontal,
+        Transform::FlipDiagonal,
+        Transform::FlipAntiDiagonal,
+    ];
+
+    /// Get the transform that reverts this transform
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::Transform;
+    /// assert_eq!(Transform::Rotate90.inverse(), Transform::Rotate270);
+    /// assert_eq!(Transform::FlipDiagonal.inverse(), Transform::FlipDiagonal);
+    /// ```
+    pub fn inverse(&self) -> Transform {
+        match self {
+            Transform::Rotate90 => Transform::Rotate270,
+            Transform::Rotate270 => Transform::Rotate90,
+            _ => *self,
+        }
+    }
+
+    /// Apply the transform to a bitboard
+    /// # Arguments
+    /// * `bits` - Bitboard to transform
+    /// # Returns
+    /// * Transformed bitboard
+    pub fn apply_bits(&self, bits: u64) -> u64 {
+        match self {
+            Transform::Identity => bits,
+            Transform::Rotate90 => flip_horizontal(flip_diagonal(bits)),
+            Transform::Rotate180 => bits.reverse_bits(),
+            Transform::Rotate270 => flip_vertical(flip_diagonal(bits)),
+            Transform::FlipVertical => flip_vertical(bits),
+            Transform::FlipHorizontal => flip_horizontal(bits),
+            Transform::FlipDiagonal => flip_diagonal(bits),
+            Transform::FlipAntiDiagonal => flip_diagonal(bits).reverse_bits(),
+        }
+    }
+
+    /// Apply the transform to a position
+    /// # Arguments
+    /// * `pos` - Position to transform (0..64)
+    /// # Returns
+    /// * `Result<usize, BoardError>` - Transformed position
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::{parse_square, Transform};
+    /// let f5 = parse_square("f5").unwrap();
+    /// let d6 = parse_square("d6").unwrap();
+    /// assert_eq!(Transform::Rotate90.apply_pos(f5).unwrap(), d6);
+    /// ```
+    /// # Note
+    /// * If the position is out of range, return Err(BoardError::InvalidPosition)
+    pub fn apply_pos(&self, pos: usize) -> Result<usize, BoardError> {
+        if pos >= BOARD_SIZE * BOARD_SIZE {
+            return Err(BoardError::InvalidPosition);
+        }
+        let n = BOARD_SIZE - 1;
+        let (r, c) = (pos / BOARD_SIZE, pos % BOARD_SIZE);
+        let (r, c) = match self {
+            Transform::Identity => (r, c),
+            Transform::Rotate90 => (c, n - r),
+            Transform::Rotate180 => (n - r, n - c),
+            Transform::Rotate270 => (n - c, r),
+            Transform::FlipVertical => (n - r, c),
+            Transform::FlipHorizontal => (r, n - c),
+            Transform::FlipDiagonal => (c, r),
+            Transform::FlipAntiDiagonal => (n - c, n - r),
+        };
+        Ok(r * BOARD_SIZE + c)
+    }
+}
+
+impl Board {
+    /// Get the board transformed by a symmetry
+    /// # Arguments
+    /// * `transform` - Transform to apply
+    /// # Returns
+    /// * Transformed board with the same turn
+    pub fn get_transformed(&self, transform: Transform) -> Board {
+        let mut board = Board::new();
+        board.set_board(
+            transform.apply_bits(self.player_board),
+            transform.apply_bits(self.opponent_board),
+            self.turn,
+        );
+        board
+    }
+
+    /// Get the canonical form of the board
+    /// # Returns
+    /// * Tuple of (canonical board, transform applied to get it)
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::{Board, Transform};
+    /// let board1 = Board::from_transcript("f5").unwrap();
+    /// let board2 = Board::from_transcript("d3").unwrap();
+    /// let (canonical1, transform1) = board1.get_canonical();
+    /// let (canonical2, _) = board2.get_canonical();
+    /// assert!(canonical1 == canonical2);
+    /// assert!(canonical1.get_transformed(transform1.inverse()) == board1);
+    /// ```
+    /// # Note
+    /// * The canonical form is the transformed board with the smallest (player_board, opponent_board)
+    /// * If several transforms give the canonical form, the first one in `Transform::ALL` is returned
+    pub fn get_canonical(&self) -> (Board, Transform) {
+        let mut best = (self.player_board, self.opponent_board);
+        let mut best_transform = Transform::Identity;
+        for &transform in Transform::ALL.iter().skip(1) {
+            let candidate = (
+                transform.apply_bits(self.player_board),
+                transform.apply_bits(self.opponent_board),
+            );
+            if candidate < best {
+                best = candidate;
+                best_transform = transform;
+            }
+        }
+        (self.get_transformed(best_transform), best_transform)
+    }
+}
@@ -0,0 +1,107 @@
+use crate::board::{Board, BoardError, Turn, NOT_COL_A, NOT_COL_H};
+
+// d4, e4, d5, e5
+const CENTER: u64 = 0x00_00_00_18_18_00_00_00;
+
+#[inline]
+fn get_neighbors(bits: u64) -> u64 {
+    let horizontal = ((bits >> 1) & NOT_COL_A) | ((bits << 1) & NOT_COL_H);
+    let bits = bits | horizontal;
+    horizontal | (bits >> 8) | (bits << 8)
+}
+
+impl Board {
+    /// Create a new board from bitboards with validation
+    /// # Arguments
+    /// * `player_board` - Bitboard of the player's stones
+    /// * `opponent_board` - Bitboard of the opponent's stones
+    /// * `turn` - Turn of the player
+    /// # Returns
+    /// * `Result<Board, BoardError>` - Board if the position is valid
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::{Board, BoardError, Turn};
+    /// let (player_board, opponent_board, turn) = Board::new().get_board();
+    /// let board = Board::from_bitboards(player_board, opponent_board, turn).unwrap();
+    /// assert!(board == Board::new());
+    /// assert!(matches!(
+    ///     Board::from_bitboards(player_board, player_board, turn),
+    ///     Err(BoardError::OverlappingStones)
+    /// ));
+    /// ```
+    /// # Note
+    /// * Errors are same as `validate`
+    pub fn from_bitboards(
+        player_board: u64,
+        opponent_board: u64,
+        turn: Turn,
+    ) -> Result<Board, BoardError> {
+        let mut board = Board::new();
+        board.try_set_board(player_board, opponent_board, turn)?;
+        Ok(board)
+    }
+
+    /// Set the current board state with validation
+    /// # Arguments
+    /// * `player_board` - Bitboard of the player's stones
+    /// * `opponent_board` - Bitboard of the opponent's stones
+    /// * `turn` - Turn of the player
+    /// # Returns
+    /// * `Result<(), BoardError>` - Ok(()) if the position is valid
+    /// # Note
+    /// * Errors are same as `validate`
+    /// * If the position is invalid, the board is not changed
+    /// * Use `set_board` to skip the validation in hot loops
+    pub fn try_set_board(
+        &mut self,
+        player_board: u64,
+        opponent_board: u64,
+        turn: Turn,
+    ) -> Result<(), BoardError> {
+        let mut board = Board::new();
+        board.set_board(player_board, opponent_board, turn);
+        board.validate()?;
+        *self = board;
+        Ok(())
+    }
+
+    /// Check if the position can be reached from the initial position
+    /// # Returns
+    /// * `Result<(), BoardError>` - Ok(()) if no problem is found
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::{Board, BoardError, Turn};
+    /// let mut board = Board::new();
+    /// assert!(board.validate().is_ok());
+    /// board.set_board(0x8000000000000000, 0x0000000000000001, Turn::Black);
+    /// assert!(matches!(board.validate(), Err(BoardError::EmptyCenter)));
+    /// ```
+    /// # Note
+    /// * If a square has both player's and opponent's stones, return Err(BoardError::OverlappingStones)
+    /// * If a center square is empty, return Err(BoardError::EmptyCenter)
+    /// * If a stone is not connected to the center through other stones,
+    ///   return Err(BoardError::DisconnectedStones)
+    /// * Passing this check does not guarantee that the position is reachable
+    pub fn validate(&self) -> Result<(), BoardError> {
+        if self.player_board & self.opponent_board != 0 {
+            return Err(BoardError::OverlappingStones);
+        }
+        let occupied = self.player_board | self.opponent_board;
+        if occupied & CENTER != CENTER {
+            return Err(BoardError::EmptyCenter);
+        }
+        // every stone is placed next to another stone
+        let mut connected = CENTER;
+        loop {
+            let next = (connected | get_neighbors(connected)) & occupied;
+            if next == connected {
+                break;
+            }
+            connected = next;
+        }
+        if connected != occupied {
+            return Err(BoardError::DisconnectedStones);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use crate::board::Turn;
+
+// Zobrist keys are indexed by color instead of player/opponent,
+// so that the key does not change when player and opponent are swapped.
+const SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+const fn splitmix64(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    (state, z ^ (z >> 31))
+}
+
+// keys[0]: black, keys[1]: white, indexed by bit number (not position)
+const SQUARE_KEYS: [[u64; 64]; 2] = {
+    let mut keys = [[0u64; 64]; 2];
+    let mut state = SEED;
+    let mut color = 0;
+    while color < 2 {
+        let mut i = 0;
+        while i < 64 {
+            let (next_state, key) = splitmix64(state);
+            state = next_state;
+            keys[color][i] = key;
+            i += 1;
+        }
+        color += 1;
+    }
+    keys
+};
+
+pub(crate) const TURN_KEY: u64 = splitmix64(!SEED).1;
+
+// Keys for each byte of a bitboard, so that a bitboard is hashed by 8 lookups.
+const fn byte_table(keys: &[u64; 64], other: Option<&[u64; 64]>) -> [[u64; 256]; 8] {
+    let mut table = [[0u64; 256]; 8];
+    let mut b = 0;
+    while b < 8 {
+        let mut v = 0;
+        while v < 256 {
+            let mut key = 0;
+            let mut k = 0;
+            while k < 8 {
+                if v & (1 << k) != 0 {
+                    key ^= keys[b * 8 + k];
+                    if let Some(other) = other {
+                        key ^= other[b * 8 + k];
+                    }
+                }
+                k += 1;
+            }
+            table[b][v] = key;
+            v += 1;
+        }
+        b += 1;
+    }
+    table
+}
+
+const BLACK_TABLE: [[u64; 256]; 8] = byte_table(&SQUARE_KEYS[0], None);
+const WHITE_TABLE: [[u64; 256]; 8] = byte_table(&SQUARE_KEYS[1], None);
+// Flipping a stone removes one color and adds the other.
+const FLIP_TABLE: [[u64; 256]; 8] = byte_table(&SQUARE_KEYS[0], Some(&SQUARE_KEYS[1]));
+
+#[inline]
+fn hash_bits(bits: u64, table: &[[u64; 256]; 8]) -> u64 {
+    let mut key = 0;
+    for (b, t) in table.iter().enumerate() {
+        key ^= t[((bits >> (b * 8)) & 0xFF) as usize];
+    }
+    key
+}
+
+/// Get the hash of the position from scratch
+#[inline]
+pub(crate) fn hash(player_board: u64, opponent_board: u64, turn: Turn) -> u64 {
+    match turn {
+        Turn::Black => {
+            hash_bits(player_board, &BLACK_TABLE) ^ hash_bits(opponent_board, &WHITE_TABLE)
+        }
+        Turn::White => {
+            hash_bits(opponent_board, &BLACK_TABLE)
+                ^ hash_bits(player_board, &WHITE_TABLE)
+                ^ TURN_KEY
+        }
+    }
+}
+
+/// Get the difference of the hash caused by placing a stone and reversing stones
+/// # Arguments
+/// * `pos_bit` - Bit of the placed stone
+/// * `flipped` - Bitboard of the reversed stones
+/// * `turn` - Turn of the player who places the stone
+/// # Note
+/// * The change of the turn is not included
+#[inline]
+pub(crate) fn move_diff(pos_bit: u64, flipped: u64, turn: Turn) -> u64 {
+    let color = match turn {
+        Turn::Black => 0,
+        Turn::White => 1,
+    };
+    SQUARE_KEYS[color][pos_bit.trailing_zeros() as usize] ^ hash_bits(flipped, &FLIP_TABLE)
+}
@@ -0,0 +1,112 @@
+use crate::board::BoardError;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum GgfError {
+    InvalidFormat,
+    InvalidBoard,
+    InvalidResult,
+    UnexpectedColor,
+    BoardError(BoardError),
+}
+
+impl From<BoardError> for GgfError {
+    fn from(e: BoardError) -> Self {
+        GgfError::BoardError(e)
+    }
+}
+
+impl fmt::Display for GgfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GgfError::InvalidFormat => write!(f, "malformed GGF game"),
+            GgfError::InvalidBoard => write!(f, "malformed GGF board (BO)"),
+            GgfError::InvalidResult => write!(f, "malformed GGF result (RE)"),
+            GgfError::UnexpectedColor => write!(f, "move played by the wrong color"),
+            GgfError::BoardError(e) => write!(f, "invalid move in GGF game: {}", e),
+        }
+    }
+}
+
+impl Error for GgfError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GgfError::BoardError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum WthorError {
+    IoError(std::io::Error),
+    InvalidHeader,
+    BoardError(BoardError),
+}
+
+impl From<std::io::Error> for WthorError {
+    fn from(e: std::io::Error) -> Self {
+        WthorError::IoError(e)
+    }
+}
+
+impl From<BoardError> for WthorError {
+    fn from(e: BoardError) -> Self {
+        WthorError::BoardError(e)
+    }
+}
+
+impl fmt::Display for WthorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WthorError::IoError(e) => write!(f, "failed to read WTHOR data: {}", e),
+            WthorError::InvalidHeader => write!(f, "malformed WTHOR header"),
+            WthorError::BoardError(e) => write!(f, "invalid move in WTHOR game: {}", e),
+        }
+    }
+}
+
+impl Error for WthorError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WthorError::IoError(e) => Some(e),
+            WthorError::BoardError(e) => Some(e),
+            WthorError::InvalidHeader => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ObfError {
+    InvalidFormat,
+    InvalidBoard,
+    InvalidScore,
+    BoardError(BoardError),
+}
+
+impl From<BoardError> for ObfError {
+    fn from(e: BoardError) -> Self {
+        ObfError::BoardError(e)
+    }
+}
+
+impl fmt::Display for ObfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ObfError::InvalidFormat => write!(f, "malformed OBF line"),
+            ObfError::InvalidBoard => write!(f, "malformed OBF board"),
+            ObfError::InvalidScore => write!(f, "malformed OBF score"),
+            ObfError::BoardError(e) => write!(f, "invalid move in OBF line: {}", e),
+        }
+    }
+}
+
+impl Error for ObfError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ObfError::BoardError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
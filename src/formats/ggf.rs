@@ -0,0 +1,450 @@
+use crate::board::{
+    format_move, parse_move, Board, Turn, BOARD_SIZE, LINE_CHAR_BLACK, LINE_CHAR_WHITE,
+};
+use crate::formats::error::GgfError;
+
+const GGF_CHAR_BLACK: char = '*';
+const GGF_CHAR_WHITE: char = 'O';
+const GGF_CHAR_EMPTY: char = '-';
+const GAME_NAME: &str = "Othello";
+
+/// How a GGF game ended
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GgfResultType {
+    /// The game was played to the end
+    Normal,
+    /// A player resigned (":r")
+    Resigned,
+    /// A player ran out of time (":t")
+    Timeout,
+    /// The score was agreed by the players (":s")
+    Agreed,
+}
+
+/// Result of a GGF game
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GgfResult {
+    score: f64,
+    result_type: GgfResultType,
+}
+
+impl GgfResult {
+    /// Create a new GgfResult instance
+    /// # Arguments
+    /// * `score` - Score from black's point of view (black pieces - white pieces)
+    /// * `result_type` - How the game ended
+    pub fn new(score: f64, result_type: GgfResultType) -> Self {
+        GgfResult { score, result_type }
+    }
+
+    /// Get the score from black's point of view
+    pub fn get_score(&self) -> f64 {
+        self.score
+    }
+
+    /// Get how the game ended
+    pub fn get_result_type(&self) -> GgfResultType {
+        self.result_type
+    }
+}
+
+/// Game in the Generic Game Format used by GGS
+/// # Note
+/// * Players (PB, PW), time control (TI), result (RE), setup position (BO)
+///   and moves (B, W) are parsed, other properties are kept as they are
+/// * Evaluations and times attached to moves are not kept
+/// * Moves are always legal moves from the setup position
+#[derive(Clone, PartialEq, Debug)]
+pub struct GgfGame {
+    black_player: Option<String>,
+    white_player: Option<String>,
+    time_control: Option<String>,
+    result: Option<GgfResult>,
+    initial_board: (u64, u64, Turn),
+    moves: Vec<Option<usize>>,
+    properties: Vec<(String, String)>,
+}
+
+impl Default for GgfGame {
+    fn default() -> Self {
+        GgfGame {
+            black_player: None,
+            white_player: None,
+            time_control: None,
+            result: None,
+            initial_board: Board::new().get_board(),
+            moves: Vec::new(),
+            properties: Vec::new(),
+        }
+    }
+}
+
+impl GgfGame {
+    /// Create a new GgfGame instance from a setup position and moves
+    /// # Arguments
+    /// * `initial_board` - Setup position
+    /// * `moves` - Moves played from the setup position, None for a pass
+    /// # Returns
+    /// * `Result<GgfGame, GgfError>` - Game without players, time control and result
+    /// # Example
+    /// ```
+    /// use rust_reversi_core::board::{parse_transcript, Board};
+    /// use rust_reversi_core::formats::{format_ggf, GgfGame};
+    /// let moves = parse_transcript("f5d6c3").unwrap();
+    /// let game = GgfGame::from_moves(&Board::new(), &moves).unwrap();
+    /// assert!(format_ggf(&game).unwrap().ends_with("B[F5]W[D6]B[C3];)"));
+    /// ```
+    /// # Note
+    /// * If a move is illegal, return Err(GgfError::BoardError)
+    pub fn from_moves(initial_board: &Board, moves: &[Option<usize>]) -> Result<Self, GgfError> {
+        initial_board.clone().do_moves(moves)?;
+        Ok(GgfGame {
+            initial_board: initial_board.get_board(),
+            moves: moves.to_vec(),
+            ..GgfGame::default()
+        })
+    }
+
+    /// Get the name of the black player (PB)
+    pub fn get_black_player(&self) -> Option<&str> {
+        self.black_player.as_deref()
+    }
+
+    /// Set the name of the black player (PB)
+    pub fn set_black_player(&mut self, name: Option<String>) {
+        self.black_player = name;
+    }
+
+    /// Get the name of the white player (PW)
+    pub fn get_white_player(&self) -> Option<&str> {
+        self.white_player.as_deref()
+    }
+
+    /// Set the name of the white player (PW)
+    pub fn set_white_player(&mut self, name: Option<String>) {
+        self.white_player = name;
+    }
+
+    /// Get the time control (TI) such as "05:00//02:00"
+    pub fn get_time_control(&self) -> Option<&str> {
+        self.time_control.as_deref()
+    }
+
+    /// Set the time control (TI)
+    pub fn set_time_control(&mut self, time_control: Option<String>) {
+        self.time_control = time_control;
+    }
+
+    /// Get the result (RE)
+    pub fn get_result(&self) -> Option<GgfResult> {
+        self.result
+    }
+
+    /// Set the result (RE)
+    pub fn set_result(&mut self, result: Option<GgfResult>) {
+        self.result = result;
+    }
+
+    /// Get a property other than GM, PB, PW, TI, RE, BO, B and W
+    /// # Arguments
+    /// * `key` - Name of the property such as "DT" or "PC"
+    pub fn get_property(&self, key: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Set a property other than GM, PB, PW, TI, RE, BO, B and W
+    /// # Arguments
+    /// * `key` - Name of the property such as "DT" or "PC"
+    /// * `value` - Value of the property
+    /// # Note
+    /// * If the property already exists, its value is replaced
+    /// * `]` and `\` in the value are escaped by `format_ggf`
+    /// * If the key is not an uppercase name or one of the parsed properties,
+    ///   return Err(GgfError::InvalidFormat)
+    pub fn set_property(&mut self, key: &str, value: &str) -> Result<(), GgfError> {
+        if !is_property_key(key)
+            || matches!(key, "GM" | "PB" | "PW" | "TI" | "RE" | "BO" | "B" | "W")
+        {
+            return Err(GgfError::InvalidFormat);
+        }
+        match self.properties.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value.to_string(),
+            None => self.properties.push((key.to_string(), value.to_string())),
+        }
+        Ok(())
+    }
+
+    /// Get the setup position (BO)
+    pub fn get_initial_board(&self) -> Board {
+        let (player_board, opponent_board, turn) = self.initial_board;
+        Board::from_bitboards_unchecked(player_board, opponent_board, turn)
+    }
+
+    /// Get the moves played from the setup position
+    /// # Returns
+    /// * List of moves, None for a pass
+    pub fn get_moves(&self) -> &[Option<usize>] {
+        &self.moves
+    }
+
+    /// Get the board after all moves are played
+    pub fn get_board(&self) -> Board {
+        let mut board = self.get_initial_board();
+        board.do_moves(&self.moves).unwrap();
+        board
+    }
+}
+
+fn is_property_key(key: &str) -> bool {
+    !key.is_empty() && key.chars().all(|c| c.is_ascii_uppercase())
+}
+
+// Escape ']' and '\\' with '\\' so that the value does not close the property
+fn escape_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == ']' || c == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// Split "value]rest" at the first unescaped ']' and unescape the value
+fn split_value(s: &str) -> Result<(String, &str), GgfError> {
+    let mut value = String::new();
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            ']' => return Ok((value, &s[i + 1..])),
+            '\\' => value.push(chars.next().ok_or(GgfError::InvalidFormat)?.1),
+            _ => value.push(c),
+        }
+    }
+    Err(GgfError::InvalidFormat)
+}
+
+fn parse_result(s: &str) -> Result<GgfResult, GgfError> {
+    let (score, result_type) = match s.trim().split_once(':') {
+        Some((score, "r")) => (score, GgfResultType::Resigned),
+        Some((score, "t")) => (score, GgfResultType::Timeout),
+        Some((score, "s")) => (score, GgfResultType::Agreed),
+        Some(_) => return Err(GgfError::InvalidResult),
+        None => (s.trim(), GgfResultType::Normal),
+    };
+    let score = score.parse::<f64>().map_err(|_| GgfError::InvalidResult)?;
+    Ok(GgfResult::new(score, result_type))
+}
+
+fn format_result(result: &GgfResult) -> String {
+    let suffix = match result.result_type {
+        GgfResultType::Normal => "",
+        GgfResultType::Resigned => ":r",
+        GgfResultType::Timeout => ":t",
+        GgfResultType::Agreed => ":s",
+    };
+    format!("{:+.3}{}", result.score, suffix)
+}
+
+fn parse_board(s: &str) -> Result<Board, GgfError> {
+    let mut tokens = s.split_whitespace();
+    if tokens.next() != Some("8") {
+        return Err(GgfError::InvalidBoard);
+    }
+    let chars: Vec<char> = tokens.flat_map(|t| t.chars()).collect();
+    if chars.len() != BOARD_SIZE * BOARD_SIZE + 1 {
+        return Err(GgfError::InvalidBoard);
+    }
+    let mut black = 0;
+    let mut white = 0;
+    for (i, &c) in chars[..BOARD_SIZE * BOARD_SIZE].iter().enumerate() {
+        let bit = 1 << (BOARD_SIZE * BOARD_SIZE - 1 - i);
+        match c {
+            GGF_CHAR_BLACK => black |= bit,
+            GGF_CHAR_WHITE => white |= bit,
+            GGF_CHAR_EMPTY => {}
+            _ => return Err(GgfError::InvalidBoard),
+        }
+    }
+    match chars[BOARD_SIZE * BOARD_SIZE] {
+        GGF_CHAR_BLACK => Ok(Board::from_bitboards_unchecked(black, white, Turn::Black)),
+        GGF_CHAR_WHITE => Ok(Board::from_bitboards_unchecked(white, black, Turn::White)),
+        _ => Err(GgfError::InvalidBoard),
+    }
+}
+
+fn format_board(board: &Board) -> Result<String, GgfError> {
+    let mut s = String::from("8");
+    for (i, c) in board.get_board_line()?.chars().enumerate() {
+        if i % BOARD_SIZE == 0 {
+            s.push(' ');
+        }
+        s.push(match c {
+            LINE_CHAR_BLACK => GGF_CHAR_BLACK,
+            LINE_CHAR_WHITE => GGF_CHAR_WHITE,
+            _ => GGF_CHAR_EMPTY,
+        });
+    }
+    s.push(' ');
+    s.push(match board.get_turn() {
+        Turn::Black => GGF_CHAR_BLACK,
+        Turn::White => GGF_CHAR_WHITE,
+    });
+    Ok(s)
+}
+
+/// Parse a game in the Generic Game Format
+/// # Arguments
+/// * `s` - Game such as "(;GM[Othello]PB[a]PW[b]RE[+2.000]BO[8 ... *]B[F5]W[D6];)"
+/// # Returns
+/// * `Result<GgfGame, GgfError>` - Parsed game
+/// # Example
+/// ```
+/// use rust_reversi_core::formats::parse_ggf;
+/// let game = parse_ggf(
+///     "(;GM[Othello]PB[alice]PW[bob]TI[05:00//02:00]RE[-2.000:r]\
+///      BO[8 -------- -------- -------- ---O*--- ---*O--- -------- -------- -------- *]\
+///      B[f5//0.01]W[d6/-1.00/0.02];)",
+/// ).unwrap();
+/// assert_eq!(game.get_black_player(), Some("alice"));
+/// assert_eq!(game.get_moves(), &[Some(37), Some(43)]);
+/// assert_eq!(game.get_result().unwrap().get_score(), -2.0);
+/// ```
+/// # Note
+/// * A missing BO means the standard initial position
+/// * `\]` and `\\` in a value are read as `]` and `\`
+/// * If a move is played by the wrong color while the side to move has to pass,
+///   the pass is inserted
+/// * If the game is malformed, return Err(GgfError::InvalidFormat)
+/// * If BO or RE is malformed, return Err(GgfError::InvalidBoard) or Err(GgfError::InvalidResult)
+/// * If a move is played by the wrong color, return Err(GgfError::UnexpectedColor)
+/// * If a move is illegal, return Err(GgfError::BoardError)
+pub fn parse_ggf(s: &str) -> Result<GgfGame, GgfError> {
+    let mut rest = s
+        .trim()
+        .strip_prefix("(;")
+        .and_then(|s| s.strip_suffix(";)"))
+        .ok_or(GgfError::InvalidFormat)?;
+    let mut game = GgfGame::default();
+    let mut initial_board = Board::new();
+    let mut colored_moves = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+        let open = rest.find('[').ok_or(GgfError::InvalidFormat)?;
+        let key = rest[..open].trim();
+        if key.contains(']') {
+            return Err(GgfError::InvalidFormat);
+        }
+        let (value, next) = split_value(&rest[open + 1..])?;
+        let value = value.as_str();
+        rest = next;
+        match key {
+            "GM" if value.trim().eq_ignore_ascii_case(GAME_NAME) => {}
+            "GM" => return Err(GgfError::InvalidFormat),
+            "PB" => game.black_player = Some(value.to_string()),
+            "PW" => game.white_player = Some(value.to_string()),
+            "TI" => game.time_control = Some(value.to_string()),
+            "RE" => game.result = Some(parse_result(value)?),
+            "BO" => initial_board = parse_board(value)?,
+            "B" | "W" => {
+                let color = if key == "B" { Turn::Black } else { Turn::White };
+                let mv = value.split('/').next().unwrap_or("");
+                colored_moves.push((color, parse_move(mv)?));
+            }
+            _ if is_property_key(key) => game.properties.push((key.to_string(), value.to_string())),
+            _ => return Err(GgfError::InvalidFormat),
+        }
+    }
+
+    let mut board = initial_board.clone();
+    let mut moves = Vec::with_capacity(colored_moves.len());
+    for (color, mv) in colored_moves {
+        if color != board.get_turn() {
+            if mv.is_some() && board.is_pass() && !board.is_game_over() {
+                board.do_pass()?;
+                moves.push(None);
+            } else {
+                return Err(GgfError::UnexpectedColor);
+            }
+        }
+        match mv {
+            Some(pos) => board.do_move(pos)?,
+            None => board.do_pass()?,
+        }
+        moves.push(mv);
+    }
+    game.initial_board = initial_board.get_board();
+    game.moves = moves;
+    Ok(game)
+}
+
+/// Parse all games in the Generic Game Format in a string
+/// # Arguments
+/// * `s` - String containing games, such as the contents of a GGF file
+/// # Returns
+/// * `Result<Vec<GgfGame>, GgfError>` - Parsed games in order
+/// # Note
+/// * Text outside of "(;" and ";)" is ignored
+/// * Errors are same as `parse_ggf`
+pub fn parse_ggf_games(s: &str) -> Result<Vec<GgfGame>, GgfError> {
+    let mut games = Vec::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("(;") {
+        let end = rest[start..].find(";)").ok_or(GgfError::InvalidFormat)? + start + 2;
+        games.push(parse_ggf(&rest[start..end])?);
+        rest = &rest[end..];
+    }
+    Ok(games)
+}
+
+/// Format a game in the Generic Game Format
+/// # Arguments
+/// * `game` - Game to format
+/// # Returns
+/// * `Result<String, GgfError>` - Game such as "(;GM[Othello]PB[a]PW[b]BO[8 ... *]B[F5];)"
+/// # Note
+/// * BO is always written, moves are written in uppercase and passes as "PA"
+/// * Properties that are not set are omitted
+/// * `]` and `\` in player names, the time control and other properties are escaped
+///   as `\]` and `\\`, which `parse_ggf` reads back
+/// * If a move is illegal, return Err(GgfError::BoardError)
+pub fn format_ggf(game: &GgfGame) -> Result<String, GgfError> {
+    let mut s = format!("(;GM[{}]", GAME_NAME);
+    for (key, value) in game.properties.iter() {
+        s.push_str(&format!("{}[{}]", key, escape_value(value)));
+    }
+    if let Some(name) = &game.black_player {
+        s.push_str(&format!("PB[{}]", escape_value(name)));
+    }
+    if let Some(name) = &game.white_player {
+        s.push_str(&format!("PW[{}]", escape_value(name)));
+    }
+    if let Some(time_control) = &game.time_control {
+        s.push_str(&format!("TI[{}]", escape_value(time_control)));
+    }
+    if let Some(result) = &game.result {
+        s.push_str(&format!("RE[{}]", format_result(result)));
+    }
+    let mut board = game.get_initial_board();
+    s.push_str(&format!("BO[{}]", format_board(&board)?));
+    for &mv in game.moves.iter() {
+        let key = match board.get_turn() {
+            Turn::Black => "B",
+            Turn::White => "W",
+        };
+        s.push_str(&format!("{}[{}]", key, format_move(mv)?.to_uppercase()));
+        match mv {
+            Some(pos) => board.do_move(pos)?,
+            None => board.do_pass()?,
+        }
+    }
+    s.push_str(";)");
+    Ok(s)
+}
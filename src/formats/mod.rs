@@ -0,0 +1,8 @@
+mod error;
+mod ggf;
+mod obf;
+mod wthor;
+pub use error::*;
+pub use ggf::{format_ggf, parse_ggf, parse_ggf_games, GgfGame, GgfResult, GgfResultType};
+pub use obf::{format_obf, parse_obf, parse_obf_lines, ObfAnnotation, ObfPosition};
+pub use wthor::{read_wthor_players, read_wthor_tournaments, WthorGame, WthorReader};
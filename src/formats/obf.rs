@@ -0,0 +1,222 @@
+use crate::board::{
+    format_move, parse_move, Board, Turn, BOARD_SIZE, LINE_CHAR_BLACK, LINE_CHAR_WHITE,
+};
+use crate::formats::error::ObfError;
+
+const MAX_SCORE: i32 = 64;
+
+/// Move annotated to an OBF position
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ObfAnnotation {
+    mv: Option<usize>,
+    score: Option<i32>,
+}
+
+impl ObfAnnotation {
+    /// Get the move
+    /// # Returns
+    /// * Some(position) for a move, None for a pass
+    pub fn get_move(&self) -> Option<usize> {
+        self.mv
+    }
+
+    /// Get the score of the move from the side to move
+    /// # Returns
+    /// * Final disc difference with perfect play, None if not annotated
+    pub fn get_score(&self) -> Option<i32> {
+        self.score
+    }
+}
+
+/// Position in the one-line OBF format used by endgame test suites and Edax
+/// # Note
+/// * A position is written as 64 squares, the side to move and annotated moves,
+///   such as "--XXXXX--OOOXX-O...-- X; G8:+18; H1:+12;"
+/// * Squares are from a1 to h8 row by row
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ObfPosition {
+    board: (u64, u64, Turn),
+    annotations: Vec<ObfAnnotation>,
+}
+
+impl ObfPosition {
+    /// Create a new ObfPosition instance without annotations
+    /// # Arguments
+    /// * `board` - Position
+    pub fn new(board: &Board) -> Self {
+        ObfPosition {
+            board: board.get_board(),
+            annotations: Vec::new(),
+        }
+    }
+
+    /// Get the position as a board
+    pub fn get_board(&self) -> Board {
+        let (player_board, opponent_board, turn) = self.board;
+        Board::from_bitboards_unchecked(player_board, opponent_board, turn)
+    }
+
+    /// Get the annotated moves in order
+    pub fn get_annotations(&self) -> &[ObfAnnotation] {
+        &self.annotations
+    }
+
+    /// Add an annotated move
+    /// # Arguments
+    /// * `mv` - Some(position) for a move, None for a pass
+    /// * `score` - Score of the move from the side to move, None if unknown
+    /// # Note
+    /// * If the move is not legal, return Err(ObfError::BoardError)
+    /// * If the score is not in -64..=64, return Err(ObfError::InvalidScore)
+    pub fn add_annotation(
+        &mut self,
+        mv: Option<usize>,
+        score: Option<i32>,
+    ) -> Result<(), ObfError> {
+        if score.is_some_and(|s| !(-MAX_SCORE..=MAX_SCORE).contains(&s)) {
+            return Err(ObfError::InvalidScore);
+        }
+        let mut board = self.get_board();
+        match mv {
+            Some(pos) => board.do_move(pos)?,
+            None => board.do_pass()?,
+        }
+        self.annotations.push(ObfAnnotation { mv, score });
+        Ok(())
+    }
+
+    /// Get the annotated move with the best score
+    /// # Returns
+    /// * Some(annotation) with the highest score, None if no move has a score
+    /// # Note
+    /// * If several moves have the best score, the first one is returned
+    pub fn get_best(&self) -> Option<ObfAnnotation> {
+        self.annotations
+            .iter()
+            .filter(|a| a.score.is_some())
+            .rev()
+            .max_by_key(|a| a.score)
+            .copied()
+    }
+}
+
+/// Parse a position in the OBF format
+/// # Arguments
+/// * `s` - Position such as "--XXXXX--OOOXX-O...-- X; G8:+18; H1:+12;"
+/// # Returns
+/// * `Result<ObfPosition, ObfError>` - Parsed position
+/// # Example
+/// ```
+/// use rust_reversi_core::board::Turn;
+/// use rust_reversi_core::formats::parse_obf;
+/// let position = parse_obf(
+///     "--XXXXX--OOOXX-O-OOOXXOX-OXOXOXXOXXXOXXX--XOXOXX-XXXOOO--OOOOO-- X; G8:+18; H1:+12;",
+/// ).unwrap();
+/// let board = position.get_board();
+/// assert_eq!(board.get_turn(), Turn::Black);
+/// assert_eq!(board.piece_sum(), 50);
+/// let best = position.get_best().unwrap();
+/// assert_eq!(best.get_move(), Some(62));
+/// assert_eq!(best.get_score(), Some(18));
+/// ```
+/// # Note
+/// * Black squares may be 'X', 'x' or '*', white squares 'O' or 'o', empty squares '-' or '.'
+/// * The side to move is 'X' or 'O', and annotations are "move:score" or "move" separated by ';'
+/// * Text after '%' is ignored as a comment
+/// * If the squares or the side to move are malformed, return Err(ObfError::InvalidBoard)
+/// * If an annotation is malformed, return Err(ObfError::InvalidFormat)
+/// * Errors of annotated moves are same as `ObfPosition::add_annotation`
+pub fn parse_obf(s: &str) -> Result<ObfPosition, ObfError> {
+    let s = s.split('%').next().unwrap_or("");
+    let mut parts = s.split(';');
+    let position: Vec<char> = parts
+        .next()
+        .unwrap_or("")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if position.len() != BOARD_SIZE * BOARD_SIZE + 1 {
+        return Err(ObfError::InvalidBoard);
+    }
+    let mut black = 0;
+    let mut white = 0;
+    for (i, &c) in position[..BOARD_SIZE * BOARD_SIZE].iter().enumerate() {
+        let bit = 1 << (BOARD_SIZE * BOARD_SIZE - 1 - i);
+        match c {
+            'X' | 'x' | '*' => black |= bit,
+            'O' | 'o' => white |= bit,
+            '-' | '.' => {}
+            _ => return Err(ObfError::InvalidBoard),
+        }
+    }
+    let board = match position[BOARD_SIZE * BOARD_SIZE] {
+        'X' | 'x' | '*' => Board::from_bitboards_unchecked(black, white, Turn::Black),
+        'O' | 'o' => Board::from_bitboards_unchecked(white, black, Turn::White),
+        _ => return Err(ObfError::InvalidBoard),
+    };
+
+    let mut obf = ObfPosition::new(&board);
+    for part in parts {
+        let part = part.trim();
+        if part.is_empty() {
+            continue;
+        }
+        let (mv, score) = match part.split_once(':') {
+            Some((mv, score)) => {
+                let score = score
+                    .trim()
+                    .parse::<i32>()
+                    .map_err(|_| ObfError::InvalidFormat)?;
+                (mv, Some(score))
+            }
+            None => (part, None),
+        };
+        let mv = parse_move(mv).map_err(|_| ObfError::InvalidFormat)?;
+        obf.add_annotation(mv, score)?;
+    }
+    Ok(obf)
+}
+
+/// Parse the positions of a test suite in the OBF format
+/// # Arguments
+/// * `s` - Positions, one per line
+/// # Returns
+/// * `Result<Vec<ObfPosition>, ObfError>` - Parsed positions in order
+/// # Note
+/// * Blank lines and lines starting with '%' or '#' are ignored
+/// * Errors are same as `parse_obf`
+pub fn parse_obf_lines(s: &str) -> Result<Vec<ObfPosition>, ObfError> {
+    s.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('%') && !line.starts_with('#'))
+        .map(parse_obf)
+        .collect()
+}
+
+/// Format a position in the OBF format
+/// # Arguments
+/// * `position` - Position to format
+/// # Returns
+/// * `Result<String, ObfError>` - Position such as "--XXXXX--OOOXX-O...-- X; G8:+18;"
+/// # Note
+/// * Moves are written in uppercase and passes as "PA"
+pub fn format_obf(position: &ObfPosition) -> Result<String, ObfError> {
+    let board = position.get_board();
+    // the squares and the turn of OBF are same as the board line
+    let mut s = board.get_board_line()?;
+    s.push(' ');
+    s.push(match board.get_turn() {
+        Turn::Black => LINE_CHAR_BLACK,
+        Turn::White => LINE_CHAR_WHITE,
+    });
+    s.push(';');
+    for annotation in position.annotations.iter() {
+        s.push(' ');
+        s.push_str(&format_move(annotation.mv)?.to_uppercase());
+        if let Some(score) = annotation.score {
+            s.push_str(&format!(":{:+}", score));
+        }
+        s.push(';');
+    }
+    Ok(s)
+}
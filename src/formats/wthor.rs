@@ -0,0 +1,306 @@
+use crate::board::{Board, BoardError, BOARD_SIZE};
+use crate::formats::error::WthorError;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+const HEADER_SIZE: usize = 16;
+const GAME_SIZE: usize = 68;
+const PLAYER_SIZE: usize = 20;
+const TOURNAMENT_SIZE: usize = 26;
+const MOVE_OFFSET: usize = 8;
+
+struct Header {
+    count: usize,
+    year: u16,
+    depth: u8,
+}
+
+// Header of .wtb, .jou and .trn files.
+// Games are counted by bytes 4..8, players and tournaments by bytes 8..10.
+fn read_header<R: Read>(reader: &mut R, is_game_file: bool) -> Result<Header, WthorError> {
+    let mut buf = [0u8; HEADER_SIZE];
+    reader.read_exact(&mut buf)?;
+    let count = match is_game_file {
+        true => u32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]) as usize,
+        false => u16::from_le_bytes([buf[8], buf[9]]) as usize,
+    };
+    let board_size = buf[12];
+    if is_game_file && board_size != 0 && board_size != BOARD_SIZE as u8 {
+        return Err(WthorError::InvalidHeader);
+    }
+    Ok(Header {
+        count,
+        year: u16::from_le_bytes([buf[10], buf[11]]),
+        depth: buf[14],
+    })
+}
+
+fn read_names<R: Read>(reader: R, record_size: usize) -> Result<Vec<String>, WthorError> {
+    let mut reader = reader;
+    let header = read_header(&mut reader, false)?;
+    let mut names = Vec::with_capacity(header.count);
+    let mut buf = vec![0u8; record_size];
+    for _ in 0..header.count {
+        reader.read_exact(&mut buf)?;
+        // names are null-terminated ISO-8859-1
+        let name: String = buf
+            .iter()
+            .take_while(|&&b| b != 0)
+            .map(|&b| b as char)
+            .collect();
+        names.push(name.trim().to_string());
+    }
+    Ok(names)
+}
+
+/// Read the player names of a WTHOR player file (.jou)
+/// # Arguments
+/// * `reader` - Contents of the file
+/// # Returns
+/// * `Result<Vec<String>, WthorError>` - Names indexed by player number
+pub fn read_wthor_players<R: Read>(reader: R) -> Result<Vec<String>, WthorError> {
+    read_names(reader, PLAYER_SIZE)
+}
+
+/// Read the tournament names of a WTHOR tournament file (.trn)
+/// # Arguments
+/// * `reader` - Contents of the file
+/// # Returns
+/// * `Result<Vec<String>, WthorError>` - Names indexed by tournament number
+pub fn read_wthor_tournaments<R: Read>(reader: R) -> Result<Vec<String>, WthorError> {
+    read_names(reader, TOURNAMENT_SIZE)
+}
+
+/// Game read from a WTHOR game file
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WthorGame {
+    tournament_id: u16,
+    black_player_id: u16,
+    white_player_id: u16,
+    tournament: Option<String>,
+    black_player: Option<String>,
+    white_player: Option<String>,
+    year: u16,
+    black_score: u8,
+    theoretical_score: u8,
+    moves: Vec<Option<usize>>,
+}
+
+impl WthorGame {
+    /// Get the tournament number
+    pub fn get_tournament_id(&self) -> u16 {
+        self.tournament_id
+    }
+
+    /// Get the black player number
+    pub fn get_black_player_id(&self) -> u16 {
+        self.black_player_id
+    }
+
+    /// Get the white player number
+    pub fn get_white_player_id(&self) -> u16 {
+        self.white_player_id
+    }
+
+    /// Get the tournament name
+    /// # Note
+    /// * None if tournament names are not set to the reader
+    pub fn get_tournament(&self) -> Option<&str> {
+        self.tournament.as_deref()
+    }
+
+    /// Get the black player name
+    /// # Note
+    /// * None if player names are not set to the reader
+    pub fn get_black_player(&self) -> Option<&str> {
+        self.black_player.as_deref()
+    }
+
+    /// Get the white player name
+    /// # Note
+    /// * None if player names are not set to the reader
+    pub fn get_white_player(&self) -> Option<&str> {
+        self.white_player.as_deref()
+    }
+
+    /// Get the year the game was played
+    pub fn get_year(&self) -> u16 {
+        self.year
+    }
+
+    /// Get the number of black pieces at the end of the game
+    /// # Note
+    /// * Empty squares are counted for the winner
+    pub fn get_black_score(&self) -> u8 {
+        self.black_score
+    }
+
+    /// Get the number of black pieces with perfect play from the search depth of the file
+    /// # Note
+    /// * See `WthorReader::get_depth`
+    pub fn get_theoretical_score(&self) -> u8 {
+        self.theoretical_score
+    }
+
+    /// Get the moves played in the game
+    /// # Returns
+    /// * List of moves, None for a pass
+    /// # Note
+    /// * Passes are not stored in WTHOR files, they are inserted while replaying
+    pub fn get_moves(&self) -> &[Option<usize>] {
+        &self.moves
+    }
+
+    /// Get the board after all moves are played
+    pub fn get_board(&self) -> Board {
+        let mut board = Board::new();
+        board.do_moves(&self.moves).unwrap();
+        board
+    }
+}
+
+/// Reader of the games in a WTHOR game file (.wtb)
+/// # Example
+/// ```no_run
+/// use rust_reversi_core::formats::{read_wthor_players, read_wthor_tournaments, WthorReader};
+/// use std::fs::File;
+/// let mut reader = WthorReader::open("WTH_2001.wtb").unwrap();
+/// reader.set_players(read_wthor_players(File::open("WTHOR.JOU").unwrap()).unwrap());
+/// reader.set_tournaments(read_wthor_tournaments(File::open("WTHOR.TRN").unwrap()).unwrap());
+/// for game in reader {
+///     let game = game.unwrap();
+///     println!("{:?} vs {:?}", game.get_black_player(), game.get_white_player());
+/// }
+/// ```
+/// # Note
+/// * Each game is replayed from the initial position, inserting passes
+/// * A game with an illegal move is returned as Err(WthorError::BoardError),
+///   and the reader continues with the next game
+pub struct WthorReader<R: Read> {
+    reader: R,
+    year: u16,
+    depth: u8,
+    game_count: usize,
+    read_count: usize,
+    players: Vec<String>,
+    tournaments: Vec<String>,
+}
+
+impl WthorReader<BufReader<File>> {
+    /// Open a WTHOR game file
+    /// # Arguments
+    /// * `path` - Path of the .wtb file
+    /// # Note
+    /// * Errors are same as `WthorReader::new`
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, WthorError> {
+        WthorReader::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> WthorReader<R> {
+    /// Create a new WthorReader instance
+    /// # Arguments
+    /// * `reader` - Contents of the .wtb file
+    /// # Returns
+    /// * `Result<WthorReader, WthorError>` - Reader positioned at the first game
+    /// # Note
+    /// * If the header cannot be read, return Err(WthorError::IoError)
+    /// * If the file is not for 8x8 boards, return Err(WthorError::InvalidHeader)
+    pub fn new(reader: R) -> Result<Self, WthorError> {
+        let mut reader = reader;
+        let header = read_header(&mut reader, true)?;
+        Ok(WthorReader {
+            reader,
+            year: header.year,
+            depth: header.depth,
+            game_count: header.count,
+            read_count: 0,
+            players: Vec::new(),
+            tournaments: Vec::new(),
+        })
+    }
+
+    /// Set the player names used for the games
+    /// # Arguments
+    /// * `players` - Names indexed by player number, see `read_wthor_players`
+    pub fn set_players(&mut self, players: Vec<String>) {
+        self.players = players;
+    }
+
+    /// Set the tournament names used for the games
+    /// # Arguments
+    /// * `tournaments` - Names indexed by tournament number, see `read_wthor_tournaments`
+    pub fn set_tournaments(&mut self, tournaments: Vec<String>) {
+        self.tournaments = tournaments;
+    }
+
+    /// Get the year of the games in the file
+    pub fn get_year(&self) -> u16 {
+        self.year
+    }
+
+    /// Get the search depth of the theoretical scores
+    pub fn get_depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Get the number of games in the file
+    pub fn get_game_count(&self) -> usize {
+        self.game_count
+    }
+
+    fn parse_game(&self, buf: &[u8; GAME_SIZE]) -> Result<WthorGame, WthorError> {
+        let tournament_id = u16::from_le_bytes([buf[0], buf[1]]);
+        let black_player_id = u16::from_le_bytes([buf[2], buf[3]]);
+        let white_player_id = u16::from_le_bytes([buf[4], buf[5]]);
+
+        let mut board = Board::new();
+        let mut moves = Vec::new();
+        for &b in buf[MOVE_OFFSET..].iter().take_while(|&&b| b != 0) {
+            // moves are stored as 10 * row + column, both starting from 1
+            let (row, col) = ((b / 10) as usize, (b % 10) as usize);
+            if !(1..=BOARD_SIZE).contains(&row) || !(1..=BOARD_SIZE).contains(&col) {
+                return Err(WthorError::BoardError(BoardError::InvalidPosition));
+            }
+            let pos = (row - 1) * BOARD_SIZE + (col - 1);
+            if board.is_pass() && !board.is_game_over() {
+                board.do_pass()?;
+                moves.push(None);
+            }
+            board.do_move(pos)?;
+            moves.push(Some(pos));
+        }
+
+        Ok(WthorGame {
+            tournament_id,
+            black_player_id,
+            white_player_id,
+            tournament: self.tournaments.get(tournament_id as usize).cloned(),
+            black_player: self.players.get(black_player_id as usize).cloned(),
+            white_player: self.players.get(white_player_id as usize).cloned(),
+            year: self.year,
+            black_score: buf[6],
+            theoretical_score: buf[7],
+            moves,
+        })
+    }
+}
+
+impl<R: Read> Iterator for WthorReader<R> {
+    type Item = Result<WthorGame, WthorError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.read_count >= self.game_count {
+            return None;
+        }
+        let mut buf = [0u8; GAME_SIZE];
+        if let Err(e) = self.reader.read_exact(&mut buf) {
+            // the rest of the file cannot be read
+            self.read_count = self.game_count;
+            return Some(Err(WthorError::from(e)));
+        }
+        self.read_count += 1;
+        Some(self.parse_game(&buf))
+    }
+}
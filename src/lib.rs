@@ -1,4 +1,5 @@
 pub mod arena;
 pub mod board;
+pub mod formats;
 pub mod search;
 pub mod utils;
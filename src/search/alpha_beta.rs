@@ -1,9 +1,13 @@
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::board::Board;
+use crate::board::{Board, Move, Square};
 use crate::search::evaluator::Evaluator;
 use crate::search::time_keeper::TimeKeeper;
-use crate::search::Search;
+use crate::search::transposition_table::{table_size, Bound, TTEntry, TTStats, TranspositionTable};
+use crate::search::{Search, SearchResult};
 use crate::utils::StackVec64;
 
 #[derive(Debug)]
@@ -13,6 +17,12 @@ pub struct AlphaBetaSearch {
     move_ordering_evaluator: Arc<dyn Evaluator>,
     win_score: i32,
     margin_time: f64,
+    tt_size: usize,
+    // statistics of the last finished search
+    tt_stats: Mutex<TTStats>,
+    pvs: bool,
+    aspiration_window: Option<i32>,
+    n_threads: usize,
 }
 
 impl AlphaBetaSearch {
@@ -26,6 +36,7 @@ impl AlphaBetaSearch {
     /// # Note
     /// * The win_score is used to determine the score of the win.
     /// * The win_score must be greater than any possible score.
+    /// * The transposition table has 65536 entries by default.
     pub fn new(max_depth: usize, evaluator: Arc<dyn Evaluator>, win_score: i32) -> Self {
         Self {
             max_depth,
@@ -33,6 +44,11 @@ impl AlphaBetaSearch {
             move_ordering_evaluator: evaluator,
             win_score,
             margin_time: DEFAULT_MARGIN_TIME,
+            tt_size: DEFAULT_TT_SIZE,
+            tt_stats: Mutex::new(TTStats::default()),
+            pvs: false,
+            aspiration_window: None,
+            n_threads: 1,
         }
     }
 
@@ -66,6 +82,125 @@ impl AlphaBetaSearch {
         self.move_ordering_evaluator = evaluator;
     }
 
+    /// Get the number of entries of the transposition table.
+    pub fn get_tt_size(&self) -> usize {
+        self.tt_size
+    }
+
+    /// Set the number of entries of the transposition table.
+    /// # Arguments
+    /// * `size` - The number of entries, rounded down to a power of two.
+    /// # Note
+    /// * Each entry uses 16 bytes.
+    /// * The table is disabled if the size is 0.
+    /// * Each search allocates its own table, so the result does not depend on
+    ///   previous searches, and searches running at the same time on the same
+    ///   instance (e.g. from several threads) do not share any entry.
+    pub fn set_tt_size(&mut self, size: usize) {
+        self.tt_size = table_size(size);
+    }
+
+    /// Get the statistics of the transposition table during the last finished search.
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    /// use rust_reversi_core::board::Board;
+    /// use rust_reversi_core::search::{AlphaBetaSearch, PieceEvaluator, Search};
+    /// let search = AlphaBetaSearch::new(4, Arc::new(PieceEvaluator::new()), 1 << 10);
+    /// let mut board = Board::new();
+    /// search.get_move(&mut board);
+    /// let stats = search.get_tt_stats();
+    /// assert!(stats.get_hits() <= stats.get_probes());
+    /// println!("hit rate: {:.3}", stats.get_hit_rate());
+    /// ```
+    /// # Note
+    /// * If searches run at the same time on the same instance,
+    ///   these are the statistics of the one that finished last.
+    pub fn get_tt_stats(&self) -> TTStats {
+        *self.tt_stats.lock().unwrap()
+    }
+
+    /// Get if the Principal Variation Search is used.
+    pub fn get_pvs(&self) -> bool {
+        self.pvs
+    }
+
+    /// Set if the Principal Variation Search (NegaScout) is used.
+    /// # Note
+    /// * The moves after the first one are searched with a null window,
+    ///   and searched again with the full window only if they may be better.
+    /// * The score is the same as the plain alpha-beta search,
+    ///   but fewer nodes are searched if the move ordering is good.
+    /// * Disabled by default.
+    pub fn set_pvs(&mut self, pvs: bool) {
+        self.pvs = pvs;
+    }
+
+    /// Get the half width of the aspiration window.
+    pub fn get_aspiration_window(&self) -> Option<i32> {
+        self.aspiration_window
+    }
+
+    /// Set the half width of the aspiration window for the iterative deepening.
+    /// # Arguments
+    /// * `window` - The half width of the window around the score of the previous iteration,
+    ///   or None to search with the full window.
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    /// use rust_reversi_core::board::Board;
+    /// use rust_reversi_core::search::{AlphaBetaSearch, PieceEvaluator, Search};
+    /// let mut search = AlphaBetaSearch::new(60, Arc::new(PieceEvaluator::new()), 1 << 10);
+    /// search.set_pvs(true);
+    /// search.set_aspiration_window(Some(4));
+    /// let mut board = Board::new();
+    /// let result = search.search_with_timeout(&mut board, Duration::from_millis(50));
+    /// assert!(result.get_best_move().is_some());
+    /// ```
+    /// # Note
+    /// * If the score is out of the window, the iteration is searched again
+    ///   with the failed side of the window opened.
+    /// * Only `get_move_with_timeout` and `search_with_timeout` use the window.
+    /// * Disabled by default.
+    pub fn set_aspiration_window(&mut self, window: Option<i32>) {
+        self.aspiration_window = window;
+    }
+
+    /// Get the number of threads.
+    pub fn get_n_threads(&self) -> usize {
+        self.n_threads
+    }
+
+    /// Set the number of threads (Lazy SMP).
+    /// # Arguments
+    /// * `n_threads` - The number of threads, 0 is treated as 1.
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    /// use rust_reversi_core::board::Board;
+    /// use rust_reversi_core::search::{AlphaBetaSearch, PieceEvaluator, Search};
+    /// let mut search = AlphaBetaSearch::new(60, Arc::new(PieceEvaluator::new()), 1 << 10);
+    /// let n_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    /// search.set_n_threads(n_threads);
+    /// let mut board = Board::new();
+    /// let m = search.get_move_with_timeout(&mut board, Duration::from_millis(50));
+    /// assert!(m.is_some());
+    /// ```
+    /// # Note
+    /// * The helper threads search the same board independently and share the
+    ///   transposition table with the main thread, whose result is returned.
+    /// * Each helper skips a different pattern of depths and starts from a different
+    ///   root move, so that the threads fill the table with different subtrees.
+    /// * The score of a search without timeout is the same as with a single thread,
+    ///   but the best move may differ among moves of the same score.
+    /// * Nodes of the result include those of all threads.
+    /// * 1 by default.
+    pub fn set_n_threads(&mut self, n_threads: usize) {
+        self.n_threads = n_threads.max(1);
+    }
+
     // Evaluate for move ordering.
     fn score_board(&self, board: &mut Board) -> i32 {
         if board.is_game_over() {
@@ -78,150 +213,298 @@ impl AlphaBetaSearch {
         self.move_ordering_evaluator.evaluate(board)
     }
 
-    fn get_child_boards_ordered(&self, board: &mut Board) -> Option<Vec<Board>> {
-        if board.is_pass() {
-            return None;
-        }
-        let mut child_boards = board.get_child_boards().unwrap();
-        child_boards.sort_by_key(|b| {
-            let mut b_clone = b.clone();
-            self.score_board(&mut b_clone)
-        });
-        Some(child_boards)
-    }
-
     fn get_legal_moves_vec_ordered(&self, board: &mut Board) -> Option<StackVec64<usize>> {
         if board.is_pass() {
             return None;
         }
         let mut legal_moves = board.get_legal_moves_vec();
-        legal_moves.sort_by_key(|&m| {
-            let mut new_board = board.clone();
-            new_board.do_move(m).unwrap();
-            self.score_board(&mut new_board)
-        });
+        legal_moves.sort_by_key(|&m| self.score_board(&mut Self::get_child(board, m)));
         Some(legal_moves)
     }
 
-    fn get_search_score(&self, board: &mut Board, depth: usize, alpha: i32, beta: i32) -> i32 {
-        if board.is_game_over() {
-            match (board.is_win(), board.is_lose()) {
-                (Ok(true), _) => return self.win_score,
-                (_, Ok(true)) => return -self.win_score,
-                _ => return 0,
-            }
-        }
-        if depth == 0 {
-            return self.evaluator.evaluate(board);
-        }
-
-        let mut current_alpha = alpha;
-        let child_boards = match (depth > 2, board.get_legal_moves().count_ones() > 4) {
-            (true, true) => self.get_child_boards_ordered(board),
-            _ => board.get_child_boards(),
-        };
-        if let Some(child_boards) = child_boards {
-            for mut child_board in child_boards {
-                let score =
-                    -self.get_search_score(&mut child_board, depth - 1, -beta, -current_alpha);
-                if score > current_alpha {
-                    current_alpha = score;
-                }
-                if current_alpha >= beta {
-                    // cut
-                    return current_alpha;
-                }
-            }
-            current_alpha
-        } else {
-            // pass
-            let mut new_board = board.clone();
-            new_board.do_pass().unwrap();
-            -self.get_search_score(&mut new_board, depth, -beta, -alpha)
-        }
+    // Board after a legal move. The legality check of `do_move` only reads
+    // the legal moves cached by the parent, which are computed before this is called.
+    fn get_child(board: &Board, m: usize) -> Board {
+        let mut child = board.clone();
+        child.do_move(m).unwrap();
+        child
     }
 
-    fn get_search_score_with_timeout(
+    fn get_search_score(
         &self,
         board: &mut Board,
         depth: usize,
         alpha: i32,
         beta: i32,
-        time_keeper: &TimeKeeper,
+        ply: usize,
+        context: &mut SearchContext,
     ) -> i32 {
-        if board.is_game_over() {
+        context.nodes += 1;
+        let is_game_over = board.is_game_over();
+        if depth == 0 && !is_game_over {
+            // the parent does not read the principal variation of a leaf
+            return self.evaluator.evaluate(board);
+        }
+        context.clear_pv(ply);
+        if is_game_over {
             match (board.is_win(), board.is_lose()) {
                 (Ok(true), _) => return self.win_score,
                 (_, Ok(true)) => return -self.win_score,
                 _ => return 0,
             }
         }
-        if depth == 0 {
-            return self.evaluator.evaluate(board);
+
+        if board.is_pass() {
+            let mut new_board = board.clone();
+            new_board.do_pass().unwrap();
+            let score =
+                -self.get_search_score(&mut new_board, depth, -beta, -alpha, ply + 1, context);
+            context.update_pv(ply, Move::Pass, true);
+            return score;
+        }
+
+        // nodes just above the leaves are cheaper to search than to look up
+        let use_tt = depth >= MIN_TT_DEPTH;
+        let hash = board.get_hash();
+        let entry = if use_tt {
+            context.tt.probe(hash, &mut context.tt_stats)
+        } else {
+            None
+        };
+        if let Some(entry) = entry.filter(|entry| entry.depth >= depth) {
+            match entry.bound {
+                Bound::Exact => return entry.score,
+                Bound::Lower if entry.score >= beta => return entry.score,
+                Bound::Upper if entry.score <= alpha => return entry.score,
+                _ => {}
+            }
         }
+        // the best move found by a shallower search is tried first
+        let tt_move = entry
+            .and_then(|entry| entry.best_move)
+            .filter(|&m| board.is_legal_move(m));
+        let tt_child = tt_move.map(|m| (m, Self::get_child(board, m)));
 
         let mut current_alpha = alpha;
-        let child_boards = match (depth > 2, board.get_legal_moves().count_ones() > 4) {
-            (true, true) => self.get_child_boards_ordered(board),
-            _ => board.get_child_boards(),
+        let mut best_move = None;
+        let ordered_moves;
+        let other_children = if depth > 2 && board.get_legal_moves().count_ones() > 4 {
+            ordered_moves = self.get_legal_moves_vec_ordered(board).unwrap();
+            Children::Ordered(
+                ordered_moves
+                    .iter()
+                    .filter(|&&m| Some(m) != tt_move)
+                    .map(|&m| (m, Self::get_child(board, m))),
+            )
+        } else {
+            Children::Unordered(
+                board
+                    .get_child_boards_iter()
+                    .filter(|&(m, _)| Some(m) != tt_move),
+            )
         };
-        if let Some(child_boards) = child_boards {
-            for mut child_board in child_boards {
-                let score = -self.get_search_score_with_timeout(
+        for (i, (m, mut child_board)) in tt_child.into_iter().chain(other_children).enumerate() {
+            let mut score = current_alpha;
+            if self.pvs && i > 0 {
+                // prove that the move is not better with a null window
+                score = -self.get_search_score(
+                    &mut child_board,
+                    depth - 1,
+                    -current_alpha - 1,
+                    -current_alpha,
+                    ply + 1,
+                    context,
+                );
+            }
+            if !(self.pvs && i > 0) || (score > current_alpha && score < beta) {
+                score = -self.get_search_score(
                     &mut child_board,
                     depth - 1,
                     -beta,
                     -current_alpha,
-                    time_keeper,
+                    ply + 1,
+                    context,
                 );
-                if score > current_alpha {
-                    current_alpha = score;
-                }
-                if current_alpha >= beta {
-                    // cut
-                    return current_alpha;
-                }
-                if time_keeper.is_timeout() {
-                    break;
-                }
             }
-            current_alpha
-        } else {
-            // pass
-            let mut new_board = board.clone();
-            new_board.do_pass().unwrap();
-            -self.get_search_score_with_timeout(&mut new_board, depth, -beta, -alpha, time_keeper)
+            if score > current_alpha {
+                current_alpha = score;
+                best_move = Some(m);
+                context.update_pv(ply, to_move(m), depth > 1);
+            }
+            if current_alpha >= beta {
+                // cut
+                break;
+            }
+            if context.is_timeout() {
+                break;
+            }
+        }
+        // the score of an interrupted search is not reliable
+        if use_tt && !context.is_timeout() {
+            let entry = TTEntry {
+                score: current_alpha,
+                depth,
+                bound: Bound::from_window(current_alpha, alpha, beta),
+                best_move: best_move.or(tt_move),
+            };
+            context.tt.store(hash, entry, &mut context.tt_stats);
         }
+        current_alpha
     }
 
-    fn get_move_with_timeout_inner(
+    // Search all moves of the root with the window and return the score.
+    // The principal variation is left in the context.
+    fn search_root(
         &self,
         board: &mut Board,
         depth: usize,
-        time_keeper: &TimeKeeper,
-    ) -> Option<usize> {
+        alpha: i32,
+        beta: i32,
+        context: &mut SearchContext,
+    ) -> i32 {
+        if board.is_game_over() || board.is_pass() {
+            // no move to choose, the board after the pass is searched as the root
+            return self.get_search_score(board, depth + 1, alpha, beta, 0, context);
+        }
+        context.nodes += 1;
+        context.clear_pv(0);
+        let hash = board.get_hash();
+        let moves = self.get_legal_moves_vec_ordered(board).unwrap();
+        // the best move of the previous iteration is tried first
+        let tt_move = context
+            .tt
+            .probe(hash, &mut context.tt_stats)
+            .and_then(|entry| entry.best_move)
+            .filter(|m| moves.contains(m));
+        let mut current_alpha = alpha;
         let mut best_move = None;
-        let mut alpha = i32::MIN + 1;
-        let beta = i32::MAX - 1;
-        for &move_i in &self.get_legal_moves_vec_ordered(board).unwrap() {
-            let mut new_board = board.clone();
-            new_board.do_move(move_i).unwrap();
-            let score = -self.get_search_score_with_timeout(
-                &mut new_board,
-                depth,
-                -beta,
-                -alpha,
-                time_keeper,
-            );
-            if score > alpha {
-                alpha = score;
+        // helper threads start from different moves to fill the table with different subtrees
+        let n_moves = moves.len();
+        let offset = context.root_offset;
+        let root_moves = (0..n_moves)
+            .map(|i| moves[(i + offset) % n_moves])
+            .filter(|&m| Some(m) != tt_move);
+        for (i, move_i) in tt_move.into_iter().chain(root_moves).enumerate() {
+            let mut new_board = Self::get_child(board, move_i);
+            let mut score = current_alpha;
+            if self.pvs && i > 0 {
+                score = -self.get_search_score(
+                    &mut new_board,
+                    depth,
+                    -current_alpha - 1,
+                    -current_alpha,
+                    1,
+                    context,
+                );
+            }
+            if !(self.pvs && i > 0) || (score > current_alpha && score < beta) {
+                score = -self.get_search_score(
+                    &mut new_board,
+                    depth,
+                    -beta,
+                    -current_alpha,
+                    1,
+                    context,
+                );
+            }
+            if score > current_alpha {
+                current_alpha = score;
                 best_move = Some(move_i);
+                context.update_pv(0, to_move(move_i), depth > 0);
             }
-            if time_keeper.is_timeout() {
+            if current_alpha >= beta || context.is_timeout() {
                 break;
             }
         }
-        best_move
+        if !context.is_timeout() {
+            let entry = TTEntry {
+                score: current_alpha,
+                depth: depth + 1,
+                bound: Bound::from_window(current_alpha, alpha, beta),
+                best_move: best_move.or(tt_move),
+            };
+            context.tt.store(hash, entry, &mut context.tt_stats);
+        }
+        current_alpha
+    }
+
+    // Search the root with a window around the score of the previous iteration,
+    // and search again with the failed side opened if the score is out of the window.
+    fn search_root_with_aspiration(
+        &self,
+        board: &mut Board,
+        depth: usize,
+        guess: i32,
+        window: i32,
+        context: &mut SearchContext,
+    ) -> i32 {
+        let mut alpha = guess.saturating_sub(window).max(i32::MIN + 1);
+        let mut beta = guess.saturating_add(window).min(i32::MAX - 1);
+        loop {
+            let score = self.search_root(board, depth, alpha, beta, context);
+            if context.is_timeout() {
+                return score;
+            }
+            if score <= alpha && alpha > i32::MIN + 1 {
+                alpha = i32::MIN + 1;
+            } else if score >= beta && beta < i32::MAX - 1 {
+                beta = i32::MAX - 1;
+            } else {
+                return score;
+            }
+        }
+    }
+
+    // Run `main` on this thread while the helper threads search the same board
+    // with iterative deepening up to `max_depth` (exclusive), filling the transposition
+    // table of this search. Returns the result of `main` and the nodes of all threads.
+    fn run_with_helpers<T>(
+        &self,
+        board: &Board,
+        max_depth: usize,
+        time_keeper: Option<&TimeKeeper>,
+        main: impl FnOnce(&mut SearchContext) -> T,
+    ) -> (T, u64) {
+        let tt = TranspositionTable::new(self.tt_size);
+        let stop = AtomicBool::new(false);
+        let (result, nodes) = thread::scope(|scope| {
+            let helpers: Vec<_> = (1..self.n_threads)
+                .map(|id| {
+                    let mut board = board.clone();
+                    let tt = &tt;
+                    let stop = &stop;
+                    scope.spawn(move || {
+                        let mut context = SearchContext::new(tt, time_keeper, Some(stop));
+                        context.root_offset = id;
+                        for depth in (0..max_depth).filter(|&depth| !is_skipped(id, depth)) {
+                            self.search_root(
+                                &mut board,
+                                depth,
+                                i32::MIN + 1,
+                                i32::MAX - 1,
+                                &mut context,
+                            );
+                            if context.is_timeout() {
+                                break;
+                            }
+                        }
+                        tt.add_stats(&context.tt_stats);
+                        context.nodes
+                    })
+                })
+                .collect();
+            let mut context = SearchContext::new(&tt, time_keeper, None);
+            let result = main(&mut context);
+            stop.store(true, Ordering::Relaxed);
+            tt.add_stats(&context.tt_stats);
+            let helper_nodes: u64 = helpers
+                .into_iter()
+                .map(|helper| helper.join().unwrap())
+                .sum();
+            (result, context.nodes + helper_nodes)
+        });
+        *self.tt_stats.lock().unwrap() = tt.get_stats();
+        (result, nodes)
     }
 
     /// Set the margin time for the search.
@@ -236,6 +519,108 @@ impl AlphaBetaSearch {
 }
 
 const DEFAULT_MARGIN_TIME: f64 = 0.005;
+const DEFAULT_TT_SIZE: usize = 1 << 16;
+const MIN_TT_DEPTH: usize = 2;
+
+// Depths skipped by the helper threads, in blocks of `SKIP_SIZE` plies shifted by `SKIP_PHASE`,
+// so that the helpers run ahead of the main thread at different depths.
+const SKIP_SIZE: [usize; 20] = [1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 4, 4, 4, 4, 4, 4, 4, 4];
+const SKIP_PHASE: [usize; 20] = [0, 1, 0, 1, 2, 3, 0, 1, 2, 3, 4, 5, 0, 1, 2, 3, 4, 5, 6, 7];
+
+// If the helper thread `id` (1..) skips the iteration of the depth
+fn is_skipped(id: usize, depth: usize) -> bool {
+    let i = (id - 1) % SKIP_SIZE.len();
+    (depth + SKIP_PHASE[i]) / SKIP_SIZE[i] % 2 == 1
+}
+
+fn to_move(m: usize) -> Move {
+    Move::Place(Square::new(m).unwrap())
+}
+
+// Children of a node, sorted by the move ordering evaluator or in the order of the legal moves
+enum Children<O, U> {
+    Ordered(O),
+    Unordered(U),
+}
+
+impl<O, U> Iterator for Children<O, U>
+where
+    O: Iterator<Item = (usize, Board)>,
+    U: Iterator<Item = (usize, Board)>,
+{
+    type Item = (usize, Board);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Children::Ordered(children) => children.next(),
+            Children::Unordered(children) => children.next(),
+        }
+    }
+}
+
+// State of a single search on one thread
+struct SearchContext<'a> {
+    // shared by all threads of the search
+    tt: &'a TranspositionTable,
+    time_keeper: Option<&'a TimeKeeper>,
+    // set when the main thread has finished, only for helper threads
+    stop: Option<&'a AtomicBool>,
+    nodes: u64,
+    // the root moves after the best move of the previous iteration are rotated by this
+    root_offset: usize,
+    // added to the transposition table at the end of the search
+    tt_stats: TTStats,
+    // principal variations from the nodes at each ply
+    pv: Vec<Vec<Move>>,
+}
+
+impl<'a> SearchContext<'a> {
+    fn new(
+        tt: &'a TranspositionTable,
+        time_keeper: Option<&'a TimeKeeper>,
+        stop: Option<&'a AtomicBool>,
+    ) -> Self {
+        Self {
+            tt,
+            time_keeper,
+            stop,
+            nodes: 0,
+            root_offset: 0,
+            tt_stats: TTStats::default(),
+            pv: Vec::new(),
+        }
+    }
+
+    fn is_timeout(&self) -> bool {
+        self.time_keeper
+            .is_some_and(|time_keeper| time_keeper.is_timeout())
+            || self.stop.is_some_and(|stop| stop.load(Ordering::Relaxed))
+    }
+
+    fn clear_pv(&mut self, ply: usize) {
+        if self.pv.len() <= ply {
+            self.pv.resize_with(ply + 1, Vec::new);
+        }
+        self.pv[ply].clear();
+    }
+
+    // The move followed by the principal variation of the next ply,
+    // which is not set if the child is a leaf.
+    fn update_pv(&mut self, ply: usize, mv: Move, has_child_pv: bool) {
+        let (pv, child_pv) = self.pv.split_at_mut(ply + 1);
+        let pv = &mut pv[ply];
+        pv.clear();
+        pv.push(mv);
+        if has_child_pv {
+            pv.extend_from_slice(&child_pv[0]);
+        }
+    }
+
+    fn take_pv(&mut self) -> Vec<Move> {
+        std::mem::take(&mut self.pv[0])
+    }
+}
 impl Search for AlphaBetaSearch {
     /// Get the best move for the given board.
     /// # Arguments
@@ -244,19 +629,7 @@ impl Search for AlphaBetaSearch {
     /// * `Some(usize)` - The best move.
     /// * `None` - player must pass.
     fn get_move(&self, board: &mut Board) -> Option<usize> {
-        let mut best_move = None;
-        let mut alpha = i32::MIN + 1;
-        let beta = i32::MAX - 1;
-        for &move_i in &self.get_legal_moves_vec_ordered(board).unwrap() {
-            let mut new_board = board.clone();
-            new_board.do_move(move_i).unwrap();
-            let score = -self.get_search_score(&mut new_board, self.max_depth, -beta, -alpha);
-            if score > alpha {
-                alpha = score;
-                best_move = Some(move_i);
-            }
-        }
-        best_move
+        self.search(board).get_best_move()
     }
 
     /// Get the best move for the given board with iterative deepening.
@@ -269,24 +642,14 @@ impl Search for AlphaBetaSearch {
     /// # Note
     /// * The search will stop if the timeout is reached or max depth is reached.
     /// * Depth will be increased iteratively from 0.
+    /// * The transposition table is kept between the iterations,
+    ///   and the best move of the previous iteration is searched first.
     fn get_move_with_timeout(
         &self,
         board: &mut Board,
         timeout: std::time::Duration,
     ) -> Option<usize> {
-        let mut best_move = None;
-        let search_duration = timeout.as_secs_f64() - self.margin_time;
-        let time_keeper = TimeKeeper::new(std::time::Duration::from_secs_f64(search_duration));
-        for depth in 0..self.max_depth {
-            let move_i = self.get_move_with_timeout_inner(board, depth, &time_keeper);
-            if time_keeper.is_timeout() {
-                break;
-            }
-            if let Some(m) = move_i {
-                best_move = Some(m);
-            }
-        }
-        best_move
+        self.search_with_timeout(board, timeout).get_best_move()
     }
 
     /// Get the search score for the given board.
@@ -297,16 +660,88 @@ impl Search for AlphaBetaSearch {
     /// # Note
     /// The search score is the score of the best move.
     fn get_search_score(&self, board: &mut Board) -> f64 {
-        let mut alpha = i32::MIN + 1;
-        let beta = i32::MAX - 1;
-        for &move_i in &self.get_legal_moves_vec_ordered(board).unwrap() {
-            let mut new_board = board.clone();
-            new_board.do_move(move_i).unwrap();
-            let score = -self.get_search_score(&mut new_board, self.max_depth, -beta, -alpha);
-            if score > alpha {
-                alpha = score;
-            }
-        }
-        alpha as f64
+        self.search(board).get_score()
+    }
+
+    /// Search the board to the max depth.
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    /// use rust_reversi_core::board::Board;
+    /// use rust_reversi_core::search::{AlphaBetaSearch, PieceEvaluator, Search};
+    /// let search = AlphaBetaSearch::new(3, Arc::new(PieceEvaluator::new()), 1 << 10);
+    /// let mut board = Board::new();
+    /// let result = search.search(&mut board);
+    /// assert_eq!(result.get_depth(), 4);
+    /// assert_eq!(result.get_pv().len(), 4);
+    /// assert_eq!(result.get_best_move(), search.get_move(&mut board));
+    /// println!("{}", result);
+    /// ```
+    /// # Note
+    /// * The depth is the number of plies, which is max depth + 1.
+    /// * Nodes are the positions visited, including the leaves.
+    fn search(&self, board: &mut Board) -> SearchResult {
+        let start = Instant::now();
+        let helper_board = board.clone();
+        let ((score, pv), nodes) =
+            self.run_with_helpers(&helper_board, self.max_depth + 1, None, |context| {
+                let score =
+                    self.search_root(board, self.max_depth, i32::MIN + 1, i32::MAX - 1, context);
+                (score, context.take_pv())
+            });
+        SearchResult::new(
+            pv,
+            score as f64,
+            self.max_depth + 1,
+            nodes,
+            start.elapsed(),
+            false,
+        )
+    }
+
+    /// Search the board with iterative deepening.
+    /// # Note
+    /// * The result is of the deepest iteration completed before the timeout.
+    /// * Nodes include those of the interrupted iteration.
+    fn search_with_timeout(&self, board: &mut Board, timeout: Duration) -> SearchResult {
+        let start = Instant::now();
+        let search_duration = timeout.as_secs_f64() - self.margin_time;
+        let time_keeper = TimeKeeper::new(Duration::from_secs_f64(search_duration));
+        let helper_board = board.clone();
+        let ((pv, score, depth_reached, interrupted), nodes) = self.run_with_helpers(
+            &helper_board,
+            self.max_depth,
+            Some(&time_keeper),
+            |context| {
+                let mut pv = Vec::new();
+                let mut score = 0;
+                let mut depth_reached = 0;
+                let mut interrupted = false;
+                for depth in 0..self.max_depth {
+                    let iteration_score = match self.aspiration_window {
+                        Some(window) if depth_reached > 0 => {
+                            self.search_root_with_aspiration(board, depth, score, window, context)
+                        }
+                        _ => self.search_root(board, depth, i32::MIN + 1, i32::MAX - 1, context),
+                    };
+                    if time_keeper.is_timeout() {
+                        interrupted = true;
+                        break;
+                    }
+                    pv = context.take_pv();
+                    score = iteration_score;
+                    depth_reached = depth + 1;
+                }
+                (pv, score, depth_reached, interrupted)
+            },
+        );
+        SearchResult::new(
+            pv,
+            score as f64,
+            depth_reached,
+            nodes,
+            start.elapsed(),
+            interrupted,
+        )
     }
 }
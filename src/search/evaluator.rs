@@ -38,12 +38,13 @@ impl LegalNumEvaluator {
 
 impl Evaluator for LegalNumEvaluator {
     fn evaluate(&self, board: &mut Board) -> i32 {
-        board.get_legal_moves_vec().len() as i32
+        board.get_legal_moves().count_ones() as i32
     }
 }
 
 /// Score is calculated by the following matrix:
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MatrixEvaluator {
     matrix: [[i32; 8]; 8],
 }
@@ -128,11 +129,51 @@ impl Evaluator for MatrixEvaluator {
 
 /// Score is calculated by the following bit patterns and weights:
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "BitMatrixData", into = "BitMatrixData")
+)]
 pub struct BitMatrixEvaluator<const N: usize> {
     weights: [i32; N],
     masks: [u64; N],
     positive_start: usize,
 }
+
+// Serialized form of BitMatrixEvaluator, the arrays are checked to have N elements.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BitMatrixData {
+    weights: Vec<i32>,
+    masks: Vec<u64>,
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> TryFrom<BitMatrixData> for BitMatrixEvaluator<N> {
+    type Error = String;
+
+    fn try_from(data: BitMatrixData) -> Result<Self, Self::Error> {
+        if data.weights.len() != N || data.masks.len() != N {
+            return Err(format!(
+                "expected {} weights and masks, got {} and {}",
+                N,
+                data.weights.len(),
+                data.masks.len()
+            ));
+        }
+        Ok(BitMatrixEvaluator::new(data.weights, data.masks))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> From<BitMatrixEvaluator<N>> for BitMatrixData {
+    fn from(evaluator: BitMatrixEvaluator<N>) -> Self {
+        BitMatrixData {
+            weights: evaluator.weights.to_vec(),
+            masks: evaluator.masks.to_vec(),
+        }
+    }
+}
 impl<const N: usize> BitMatrixEvaluator<N> {
     /// Create a new BitMatrixEvaluator instance.
     /// # Arguments
@@ -1,7 +1,23 @@
 use crate::board::Board;
+use crate::search::playout_tree::{game_value, new_rng, PlayoutNode};
 use crate::search::time_keeper::TimeKeeper;
-use crate::search::Search;
-use std::time::Duration;
+use crate::search::{Search, SearchResult};
+use rand::rngs::StdRng;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Boards of the children of a node that is not over.
+fn child_boards(board: &Board) -> Vec<Board> {
+    let mut board = board.clone();
+    if !board.is_pass() {
+        board.get_child_boards_iter().map(|(_, b)| b).collect()
+    } else {
+        board.do_pass().unwrap();
+        vec![board]
+    }
+}
 
 struct MctsNode {
     board: Board,
@@ -31,28 +47,24 @@ impl MctsNode {
         if self.board.is_game_over() {
             panic!("MctsNode::expand called on a node that is a terminal node.");
         }
-        if let Some(children) = self.board.get_child_boards() {
-            self.children = Some(
-                children
-                    .into_iter()
-                    .map(|b| MctsNode::new(b, self.c, self.expansion_threshold))
-                    .collect(),
-            );
-        } else {
-            let mut board = self.board.clone();
-            board.do_pass().unwrap();
-            self.children = Some(vec![MctsNode::new(board, self.c, self.expansion_threshold)]);
-        }
+        let (c, expansion_threshold) = (self.c, self.expansion_threshold);
+        self.children = Some(
+            child_boards(&self.board)
+                .into_iter()
+                .map(|b| MctsNode::new(b, c, expansion_threshold))
+                .collect(),
+        );
     }
 
-    fn play_out(board: &Board) -> f64 {
+    // Value of a random playout for the player to move, same values as `game_value`.
+    fn play_out(board: &Board, rng: &mut StdRng) -> f64 {
         let mut board = board.clone();
         let node_turn = board.get_turn();
         while !board.is_game_over() {
             if board.is_pass() {
                 board.do_pass().unwrap();
             } else {
-                let m = board.get_random_move().unwrap();
+                let m = board.get_random_move_with_rng(rng).unwrap();
                 board.do_move(m).unwrap();
             }
         }
@@ -91,23 +103,14 @@ impl MctsNode {
         best_child_index
     }
 
-    fn evaluate(&mut self) -> f64 {
+    fn evaluate(&mut self, rng: &mut StdRng) -> f64 {
         if self.board.is_game_over() {
-            let value = match self.board.get_winner().unwrap() {
-                Some(winner) => {
-                    if winner == self.board.get_turn() {
-                        1.0
-                    } else {
-                        0.0
-                    }
-                }
-                None => 0.5,
-            };
+            let value = game_value(&self.board);
             self.w += value;
             self.n_visits += 1;
             value
         } else if self.children.is_none() {
-            let value = Self::play_out(&self.board);
+            let value = Self::play_out(&self.board, rng);
             self.w += value;
             self.n_visits += 1;
 
@@ -118,7 +121,7 @@ impl MctsNode {
             value
         } else {
             let child_index = self.select_child_index();
-            let value = 1.0 - self.children.as_mut().unwrap()[child_index].evaluate();
+            let value = 1.0 - self.children.as_mut().unwrap()[child_index].evaluate(rng);
             self.w += value;
             self.n_visits += 1;
             value
@@ -126,6 +129,136 @@ impl MctsNode {
     }
 }
 
+impl PlayoutNode for MctsNode {
+    fn get_board_mut(&mut self) -> &mut Board {
+        &mut self.board
+    }
+
+    fn get_n_visits(&self) -> usize {
+        self.n_visits
+    }
+
+    fn get_children(&self) -> Option<&[Self]> {
+        self.children.as_deref()
+    }
+
+    fn get_children_mut(&mut self) -> Option<&mut [Self]> {
+        self.children.as_deref_mut()
+    }
+}
+
+// Node of a tree searched by several threads at once.
+// The wins are counted in halves to be updated atomically, and the threads
+// descending through a node add virtual losses to it until their playouts are
+// backed up, so the other threads are led to different nodes.
+struct SharedMctsNode {
+    board: Board,
+    half_w: AtomicU64,
+    n_visits: AtomicUsize,
+    virtual_losses: AtomicUsize,
+    children: OnceLock<Vec<SharedMctsNode>>,
+}
+
+impl SharedMctsNode {
+    fn new(board: Board) -> Self {
+        Self {
+            board,
+            half_w: AtomicU64::new(0),
+            n_visits: AtomicUsize::new(0),
+            virtual_losses: AtomicUsize::new(0),
+            children: OnceLock::new(),
+        }
+    }
+
+    fn expand(&self) {
+        self.children.get_or_init(|| {
+            child_boards(&self.board)
+                .into_iter()
+                .map(SharedMctsNode::new)
+                .collect()
+        });
+    }
+
+    // Visits and wins of the node from the parent's view, with the virtual losses.
+    fn get_stats(&self) -> (f64, f64) {
+        let virtual_losses = self.virtual_losses.load(Ordering::Relaxed) as f64;
+        let n_visits = self.n_visits.load(Ordering::Relaxed) as f64 + virtual_losses;
+        let w = self.half_w.load(Ordering::Relaxed) as f64 / 2.0 + virtual_losses;
+        (n_visits, n_visits - w)
+    }
+
+    fn select_child_index(children: &[SharedMctsNode], c: f64) -> usize {
+        let mut t: f64 = 0.0;
+        for (i, child) in children.iter().enumerate() {
+            let (n_visits, _) = child.get_stats();
+            if n_visits == 0.0 {
+                return i;
+            }
+            t += n_visits;
+        }
+        let mut best_child_index = 0;
+        let mut best_ucb = f64::NEG_INFINITY;
+        for (i, child) in children.iter().enumerate() {
+            let (n_visits, w) = child.get_stats();
+            let ucb = w / n_visits + c * (2.0 * t.ln() / n_visits).sqrt();
+            if ucb > best_ucb {
+                best_ucb = ucb;
+                best_child_index = i;
+            }
+        }
+        best_child_index
+    }
+
+    fn evaluate(&self, c: f64, expansion_threshold: usize, rng: &mut StdRng) -> f64 {
+        let value = if self.board.is_game_over() {
+            game_value(&self.board)
+        } else if let Some(children) = self.children.get() {
+            let child = &children[Self::select_child_index(children, c)];
+            child.virtual_losses.fetch_add(1, Ordering::Relaxed);
+            let value = 1.0 - child.evaluate(c, expansion_threshold, rng);
+            child.virtual_losses.fetch_sub(1, Ordering::Relaxed);
+            value
+        } else {
+            MctsNode::play_out(&self.board, rng)
+        };
+        debug_assert!(value == 0.0 || value == 0.5 || value == 1.0);
+        self.half_w
+            .fetch_add((value * 2.0) as u64, Ordering::Relaxed);
+        let n_visits = self.n_visits.fetch_add(1, Ordering::Relaxed) + 1;
+        if n_visits >= expansion_threshold && !self.board.is_game_over() {
+            self.expand();
+        }
+        value
+    }
+
+    // Convert to a node of a single thread after the search.
+    fn into_node(self, c: f64, expansion_threshold: usize) -> MctsNode {
+        MctsNode {
+            board: self.board,
+            c,
+            expansion_threshold,
+            w: self.half_w.into_inner() as f64 / 2.0,
+            n_visits: self.n_visits.into_inner(),
+            children: self.children.into_inner().map(|children| {
+                children
+                    .into_iter()
+                    .map(|child| child.into_node(c, expansion_threshold))
+                    .collect()
+            }),
+        }
+    }
+}
+
+/// How `MctsSearch` uses several threads
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum MctsParallelMode {
+    /// All threads search one tree, with virtual losses on the nodes being searched
+    #[default]
+    Tree,
+    /// Each thread searches its own tree, and the visits of the root's children are summed
+    Root,
+}
+
 /// The Monte Carlo Tree Search Search.
 #[derive(Debug)]
 pub struct MctsSearch {
@@ -134,12 +267,15 @@ pub struct MctsSearch {
     expansion_threshold: usize,
     margin_time: f64,
     check_interval: usize,
+    seed: Option<u64>,
+    n_threads: usize,
+    parallel_mode: MctsParallelMode,
 }
 
 impl MctsSearch {
     /// Create a new MctsSearch instance.
     /// # Arguments
-    /// * `n_playouts` - The number of playouts to run.
+    /// * `n_playouts` - The number of playouts to run, 0 is treated as 1.
     /// * `c` - The exploration parameter.
     /// * `expansion_threshold` - The number of visits to expand the node.
     /// * `margin_time` - The margin time to stop the search.
@@ -148,11 +284,14 @@ impl MctsSearch {
     /// A new MctsSearch instance.
     pub fn new(n_playouts: usize, c: f64, expansion_threshold: usize) -> Self {
         Self {
-            n_playouts,
+            n_playouts: n_playouts.max(1),
             c,
             expansion_threshold,
             margin_time: DEFAULT_MARGIN_TIME,
             check_interval: DEFAULT_CHECK_INTERVAL,
+            seed: None,
+            n_threads: 1,
+            parallel_mode: MctsParallelMode::Tree,
         }
     }
 
@@ -162,8 +301,10 @@ impl MctsSearch {
     }
 
     /// Set the number of playouts to run.
+    /// # Note
+    /// * 0 is treated as 1, so that a move is always chosen by at least one playout.
     pub fn set_n_playouts(&mut self, n_playouts: usize) {
-        self.n_playouts = n_playouts;
+        self.n_playouts = n_playouts.max(1);
     }
 
     /// Get the exploration parameter.
@@ -205,6 +346,222 @@ impl MctsSearch {
     pub fn set_check_interval(&mut self, check_interval: usize) {
         self.check_interval = check_interval;
     }
+
+    /// Get the seed of the random number generator.
+    pub fn get_seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Set the seed of the random number generator.
+    /// # Note
+    /// * With a seed, each search starts from the same random state,
+    ///   so the same board gives the same playouts and the same move.
+    /// * Searches with a timeout are reproducible only if they are not stopped by the timeout.
+    /// * None uses a new random state for each search (default).
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+    }
+
+    /// Get the number of threads.
+    pub fn get_n_threads(&self) -> usize {
+        self.n_threads
+    }
+
+    /// Set the number of threads.
+    /// # Arguments
+    /// * `n_threads` - The number of threads, 0 is treated as 1.
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use rust_reversi_core::board::Board;
+    /// use rust_reversi_core::search::{MctsSearch, Search};
+    /// let mut search = MctsSearch::new(1_000_000, 1.0, 10);
+    /// let n_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    /// search.set_n_threads(n_threads);
+    /// let mut board = Board::new();
+    /// let m = search.get_move_with_timeout(&mut board, Duration::from_millis(50));
+    /// assert!(m.is_some());
+    /// ```
+    /// # Note
+    /// * The playouts are shared among the threads, so the number of playouts is the same.
+    /// * A search of several threads is not reproducible in the tree mode even with a seed,
+    ///   because the threads are not synchronized.
+    /// * 1 by default, which searches without spawning threads.
+    pub fn set_n_threads(&mut self, n_threads: usize) {
+        self.n_threads = n_threads.max(1);
+    }
+
+    /// Get how several threads are used.
+    pub fn get_parallel_mode(&self) -> MctsParallelMode {
+        self.parallel_mode
+    }
+
+    /// Set how several threads are used.
+    /// # Note
+    /// * `MctsParallelMode::Tree` by default.
+    /// * Ignored with a single thread.
+    pub fn set_parallel_mode(&mut self, parallel_mode: MctsParallelMode) {
+        self.parallel_mode = parallel_mode;
+    }
+
+    fn is_timeout(&self, i: usize, time_keeper: Option<&TimeKeeper>) -> bool {
+        i % self.check_interval == 0
+            && time_keeper.is_some_and(|time_keeper| time_keeper.is_timeout())
+    }
+
+    // Run the playouts of one thread on its own tree.
+    fn run_node(
+        &self,
+        root: &mut MctsNode,
+        n_playouts: usize,
+        time_keeper: Option<&TimeKeeper>,
+        thread_id: usize,
+    ) -> usize {
+        let mut rng = new_rng(self.seed, thread_id);
+        for i in 0..n_playouts {
+            root.evaluate(&mut rng);
+            if self.is_timeout(i, time_keeper) {
+                return i + 1;
+            }
+        }
+        n_playouts
+    }
+
+    // Run the playouts with all threads on one tree.
+    fn run_tree_parallel(
+        &self,
+        board: &Board,
+        time_keeper: Option<&TimeKeeper>,
+    ) -> (MctsNode, usize) {
+        let root = SharedMctsNode::new(board.clone());
+        root.expand();
+        let next_playout = AtomicUsize::new(0);
+        let n_playouts: usize = thread::scope(|scope| {
+            let handles: Vec<_> = (0..self.n_threads)
+                .map(|thread_id| {
+                    let (root, next_playout) = (&root, &next_playout);
+                    scope.spawn(move || {
+                        let mut rng = new_rng(self.seed, thread_id);
+                        let mut n_playouts = 0;
+                        loop {
+                            let i = next_playout.fetch_add(1, Ordering::Relaxed);
+                            if i >= self.n_playouts {
+                                break;
+                            }
+                            root.evaluate(self.c, self.expansion_threshold, &mut rng);
+                            n_playouts += 1;
+                            if self.is_timeout(n_playouts - 1, time_keeper) {
+                                // stop the other threads too
+                                next_playout.fetch_max(self.n_playouts, Ordering::Relaxed);
+                                break;
+                            }
+                        }
+                        n_playouts
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).sum()
+        });
+        (root.into_node(self.c, self.expansion_threshold), n_playouts)
+    }
+
+    // Run the playouts with a tree for each thread, and merge the trees.
+    fn run_root_parallel(
+        &self,
+        board: &Board,
+        time_keeper: Option<&TimeKeeper>,
+    ) -> (MctsNode, usize, usize) {
+        let results: Vec<(MctsNode, usize)> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..self.n_threads)
+                .map(|thread_id| {
+                    let n_playouts = self.n_playouts / self.n_threads
+                        + usize::from(thread_id < self.n_playouts % self.n_threads);
+                    scope.spawn(move || {
+                        let mut root =
+                            MctsNode::new(board.clone(), self.c, self.expansion_threshold);
+                        root.expand();
+                        let n_playouts =
+                            self.run_node(&mut root, n_playouts, time_keeper, thread_id);
+                        (root, n_playouts)
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        let depth = results
+            .iter()
+            .map(|(root, _)| root.get_depth())
+            .max()
+            .unwrap();
+        let n_playouts = results.iter().map(|&(_, n_playouts)| n_playouts).sum();
+        let mut roots = results.into_iter().map(|(root, _)| root);
+        let mut merged = roots.next().unwrap();
+        let mut other_children = Vec::new();
+        for root in roots {
+            merged.w += root.w;
+            merged.n_visits += root.n_visits;
+            other_children.push(root.children.unwrap());
+        }
+        // sum the visits of each child, and keep its most visited subtree
+        // for the principal variation
+        for (i, merged_child) in merged.children.as_mut().unwrap().iter_mut().enumerate() {
+            let (mut w, mut n_visits) = (merged_child.w, merged_child.n_visits);
+            for children in other_children.iter_mut() {
+                let child = &mut children[i];
+                w += child.w;
+                n_visits += child.n_visits;
+                if child.n_visits > merged_child.n_visits {
+                    std::mem::swap(merged_child, child);
+                }
+            }
+            merged_child.w = w;
+            merged_child.n_visits = n_visits;
+        }
+        // every playout of a root goes through one of its children
+        debug_assert_eq!(
+            merged
+                .children
+                .as_ref()
+                .unwrap()
+                .iter()
+                .map(|child| child.n_visits)
+                .sum::<usize>(),
+            merged.n_visits
+        );
+        (merged, n_playouts, depth)
+    }
+
+    // Run the playouts until the number of playouts or the timeout is reached.
+    fn run(&self, board: &mut Board, time_keeper: Option<&TimeKeeper>) -> SearchResult {
+        let start = Instant::now();
+        if board.is_game_over() {
+            return SearchResult::new(Vec::new(), game_value(board), 0, 0, start.elapsed(), false);
+        }
+        let (mut root, n_playouts, depth) = match (self.n_threads, self.parallel_mode) {
+            (1, _) => {
+                let mut root = MctsNode::new(board.clone(), self.c, self.expansion_threshold);
+                root.expand();
+                let n_playouts = self.run_node(&mut root, self.n_playouts, time_keeper, 0);
+                let depth = root.get_depth();
+                (root, n_playouts, depth)
+            }
+            (_, MctsParallelMode::Tree) => {
+                let (root, n_playouts) = self.run_tree_parallel(board, time_keeper);
+                let depth = root.get_depth();
+                (root, n_playouts, depth)
+            }
+            (_, MctsParallelMode::Root) => self.run_root_parallel(board, time_keeper),
+        };
+        debug_assert_eq!(root.n_visits, n_playouts);
+        SearchResult::new(
+            root.get_pv(),
+            root.w / root.n_visits as f64,
+            depth,
+            n_playouts as u64,
+            start.elapsed(),
+            n_playouts < self.n_playouts,
+        )
+    }
 }
 
 const DEFAULT_MARGIN_TIME: f64 = 0.002;
@@ -218,21 +575,7 @@ impl Search for MctsSearch {
     /// `Some(usize)` - The best move.
     /// `None` - player must pass.
     fn get_move(&self, board: &mut Board) -> Option<usize> {
-        let mut root = MctsNode::new(board.clone(), self.c, self.expansion_threshold);
-        root.expand();
-        for _ in 0..self.n_playouts {
-            root.evaluate();
-        }
-        let mut best_child_index = 0;
-        let mut best_n_visits = 0;
-        for (i, child) in root.children.as_ref().unwrap().iter().enumerate() {
-            if child.n_visits > best_n_visits {
-                best_n_visits = child.n_visits;
-                best_child_index = i;
-            }
-        }
-        let legal_moves = board.get_legal_moves_vec();
-        Some(legal_moves[best_child_index])
+        self.search(board).get_best_move()
     }
 
     /// Get the best move for the given board with a timeout.
@@ -247,26 +590,7 @@ impl Search for MctsSearch {
     /// The search will be stopped when the timeout is reached or the number of playouts is reached.
     /// If you want to stop the search when the timeout is reached, set the timeout to a bigger value.
     fn get_move_with_timeout(&self, board: &mut Board, timeout: Duration) -> Option<usize> {
-        let mut root = MctsNode::new(board.clone(), self.c, self.expansion_threshold);
-        root.expand();
-        let search_duration = timeout.as_secs_f64() - self.margin_time;
-        let time_keeper = TimeKeeper::new(Duration::from_secs_f64(search_duration));
-        for i in 0..self.n_playouts {
-            root.evaluate();
-            if i % self.check_interval == 0 && time_keeper.is_timeout() {
-                break;
-            }
-        }
-        let mut best_child_index = 0;
-        let mut best_n_visits = 0;
-        for (i, child) in root.children.as_ref().unwrap().iter().enumerate() {
-            if child.n_visits > best_n_visits {
-                best_n_visits = child.n_visits;
-                best_child_index = i;
-            }
-        }
-        let legal_moves = board.get_legal_moves_vec();
-        Some(legal_moves[best_child_index])
+        self.search_with_timeout(board, timeout).get_best_move()
     }
 
     /// Get the search score for the given board.
@@ -278,18 +602,22 @@ impl Search for MctsSearch {
     /// The search score is the win rate of the best move.
     /// The win rate is calculated by the number of wins divided by the number of visits.
     fn get_search_score(&self, board: &mut Board) -> f64 {
-        if board.is_game_over() {
-            return match (board.is_win(), board.is_lose()) {
-                (Ok(true), _) => 1.0,
-                (_, Ok(true)) => 0.0,
-                _ => 0.5,
-            };
-        }
-        let mut root = MctsNode::new(board.clone(), self.c, self.expansion_threshold);
-        root.expand();
-        for _ in 0..self.n_playouts {
-            root.evaluate();
-        }
-        root.w / root.n_visits as f64
+        self.search(board).get_score()
+    }
+
+    /// Search the board with the number of playouts.
+    /// # Note
+    /// * The principal variation follows the most visited children.
+    /// * The score is the win rate of the board, same as `get_search_score`.
+    /// * Nodes are the number of playouts.
+    fn search(&self, board: &mut Board) -> SearchResult {
+        self.run(board, None)
+    }
+
+    /// Search the board with the number of playouts or until the timeout.
+    fn search_with_timeout(&self, board: &mut Board, timeout: Duration) -> SearchResult {
+        let search_duration = timeout.as_secs_f64() - self.margin_time;
+        let time_keeper = TimeKeeper::new(Duration::from_secs_f64(search_duration));
+        self.run(board, Some(&time_keeper))
     }
 }
@@ -1,10 +1,15 @@
 mod alpha_beta;
 mod evaluator;
 mod mcts;
+mod playout_tree;
+mod position_generator;
+mod search_result;
 mod thunder;
 mod time_keeper;
+mod transposition_table;
 mod winrate_evaluator;
 use std::fmt::Debug;
+use std::time::Instant;
 
 pub use alpha_beta::AlphaBetaSearch;
 pub use evaluator::BitMatrixEvaluator;
@@ -12,11 +17,15 @@ pub use evaluator::Evaluator;
 pub use evaluator::LegalNumEvaluator;
 pub use evaluator::MatrixEvaluator;
 pub use evaluator::PieceEvaluator;
+pub use mcts::MctsParallelMode;
 pub use mcts::MctsSearch;
+pub use position_generator::PositionGenerator;
+pub use search_result::SearchResult;
 pub use thunder::ThunderSearch;
+pub use transposition_table::TTStats;
 pub use winrate_evaluator::WinrateEvaluator;
 
-use crate::board::Board;
+use crate::board::{Board, Move, Square};
 
 pub trait Search: Debug {
     fn get_move(&self, board: &mut Board) -> Option<usize>;
@@ -26,4 +35,59 @@ pub trait Search: Debug {
         timeout: std::time::Duration,
     ) -> Option<usize>;
     fn get_search_score(&self, board: &mut Board) -> f64;
+
+    /// Search the board and report the principal variation and statistics
+    /// # Note
+    /// * The default implementation only reports the best move of `get_move`,
+    ///   the score of `get_search_score` and the elapsed time, with depth and nodes 0
+    fn search(&self, board: &mut Board) -> SearchResult {
+        let start = Instant::now();
+        let pv = self.get_typed_move(board).into_iter().collect();
+        let score = self.get_search_score(board);
+        SearchResult::new(pv, score, 0, 0, start.elapsed(), false)
+    }
+
+    /// Search the board within the timeout and report the principal variation and statistics
+    /// # Note
+    /// * The default implementation only reports the best move of `get_move_with_timeout`
+    ///   and the elapsed time, with score NaN and depth and nodes 0
+    fn search_with_timeout(&self, board: &mut Board, timeout: std::time::Duration) -> SearchResult {
+        let start = Instant::now();
+        let pv = self
+            .get_typed_move_with_timeout(board, timeout)
+            .into_iter()
+            .collect();
+        SearchResult::new(pv, f64::NAN, 0, 0, start.elapsed(), false)
+    }
+
+    /// Get the best move as a Move
+    /// # Returns
+    /// * `Some(Move::Pass)` - The player must pass, returned without searching
+    /// * `Some(Move::Place(square))` - The best move
+    /// * `None` - The game is over, or the search found no move
+    fn get_typed_move(&self, board: &mut Board) -> Option<Move> {
+        if board.is_pass() && !board.is_game_over() {
+            return Some(Move::Pass);
+        }
+        to_typed_move(self.get_move(board))
+    }
+
+    /// Get the best move as a Move within the timeout
+    /// # Returns
+    /// * Same as `get_typed_move`
+    fn get_typed_move_with_timeout(
+        &self,
+        board: &mut Board,
+        timeout: std::time::Duration,
+    ) -> Option<Move> {
+        if board.is_pass() && !board.is_game_over() {
+            return Some(Move::Pass);
+        }
+        to_typed_move(self.get_move_with_timeout(board, timeout))
+    }
+}
+
+// A position that is not a square is not a move either
+fn to_typed_move(pos: Option<usize>) -> Option<Move> {
+    pos.and_then(|pos| Square::new(pos).ok()).map(Move::Place)
 }
@@ -0,0 +1,82 @@
+use crate::board::{Board, Move};
+use crate::utils::BitIter;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// Value of a finished game for the player to move.
+// Always 0.0, 0.5 or 1.0, which the nodes of the tree-parallel MCTS count in halves.
+pub(crate) fn game_value(board: &Board) -> f64 {
+    match board.get_winner().unwrap() {
+        Some(winner) => {
+            if winner == board.get_turn() {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        None => 0.5,
+    }
+}
+
+// Random number generator of a search. With a seed, each stream (such as a thread)
+// uses the seed following the previous one.
+pub(crate) fn new_rng(seed: Option<u64>, stream: usize) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(stream as u64)),
+        None => StdRng::from_entropy(),
+    }
+}
+
+// Node of a tree grown by playouts, with the parts common to MCTS and Thunder search.
+// The children are in the order of the legal moves, or a single child for a pass.
+pub(crate) trait PlayoutNode: Sized {
+    fn get_board_mut(&mut self) -> &mut Board;
+    fn get_n_visits(&self) -> usize;
+    fn get_children(&self) -> Option<&[Self]>;
+    fn get_children_mut(&mut self) -> Option<&mut [Self]>;
+
+    fn get_best_child_index(&self) -> usize {
+        let mut best_child_index = 0;
+        let mut best_n_visits = 0;
+        for (i, child) in self.get_children().unwrap().iter().enumerate() {
+            if child.get_n_visits() > best_n_visits {
+                best_n_visits = child.get_n_visits();
+                best_child_index = i;
+            }
+        }
+        best_child_index
+    }
+
+    // Follow the most visited children.
+    fn get_pv(&mut self) -> Vec<Move> {
+        let mut pv = Vec::new();
+        let mut node = self;
+        while node.get_children().is_some() {
+            let best_child_index = node.get_best_child_index();
+            let legal_moves = node.get_board_mut().get_legal_moves();
+            let child = &mut node.get_children_mut().unwrap()[best_child_index];
+            if child.get_n_visits() == 0 {
+                break;
+            }
+            pv.push(match BitIter::new(legal_moves).nth(best_child_index) {
+                Some(m) => Move::try_from(Some(m)).unwrap(),
+                None => Move::Pass,
+            });
+            node = child;
+        }
+        pv
+    }
+
+    fn get_depth(&self) -> usize {
+        match self.get_children() {
+            Some(children) => {
+                1 + children
+                    .iter()
+                    .map(|child| child.get_depth())
+                    .max()
+                    .unwrap_or(0)
+            }
+            None => 0,
+        }
+    }
+}
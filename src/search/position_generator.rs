@@ -0,0 +1,219 @@
+use std::sync::Arc;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::board::{Board, BOARD_SIZE};
+use crate::search::Evaluator;
+use crate::utils::StackVec64;
+
+const DEFAULT_TEMPERATURE: f64 = 1.0;
+const DEFAULT_MAX_ATTEMPTS: usize = 1000;
+const MAX_EMPTIES: usize = BOARD_SIZE * BOARD_SIZE - 4;
+
+/// Generator of random positions reached by playouts from the initial position
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use rust_reversi_core::search::PieceEvaluator;
+/// use rust_reversi_core::search::PositionGenerator;
+/// let mut generator = PositionGenerator::new(20, 42);
+/// generator.set_balance_evaluator(Some(Arc::new(PieceEvaluator::new())));
+/// generator.set_max_imbalance(10);
+/// for board in generator.generate_n(10) {
+///     assert_eq!(board.piece_sum(), 44);
+///     assert!(board.diff_piece_num().abs() <= 10);
+///     assert!(!board.is_pass());
+/// }
+/// ```
+/// # Note
+/// * The same seed generates the same positions, if the evaluators are deterministic
+/// * Moves are chosen uniformly at random, or weighted by `playout_evaluator` if it is set
+/// * The side to move of a generated position always has a legal move
+#[derive(Debug)]
+pub struct PositionGenerator {
+    n_empties: usize,
+    rng: StdRng,
+    playout_evaluator: Option<Arc<dyn Evaluator>>,
+    temperature: f64,
+    balance_evaluator: Option<Arc<dyn Evaluator>>,
+    max_imbalance: i32,
+    max_attempts: usize,
+}
+
+impl PositionGenerator {
+    /// Create a new PositionGenerator instance
+    /// # Arguments
+    /// * `n_empties` - Number of empty squares of the generated positions (1..=60)
+    /// * `seed` - Seed of the random number generator
+    /// # Note
+    /// * If `n_empties` is out of range, no position is generated
+    pub fn new(n_empties: usize, seed: u64) -> Self {
+        Self {
+            n_empties,
+            rng: StdRng::seed_from_u64(seed),
+            playout_evaluator: None,
+            temperature: DEFAULT_TEMPERATURE,
+            balance_evaluator: None,
+            max_imbalance: 0,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+        }
+    }
+
+    /// Get the number of empty squares of the generated positions
+    pub fn get_n_empties(&self) -> usize {
+        self.n_empties
+    }
+
+    /// Set the number of empty squares of the generated positions
+    pub fn set_n_empties(&mut self, n_empties: usize) {
+        self.n_empties = n_empties;
+    }
+
+    /// Get the evaluator used to weight the moves of the playouts
+    pub fn get_playout_evaluator(&self) -> Option<Arc<dyn Evaluator>> {
+        self.playout_evaluator.clone()
+    }
+
+    /// Set the evaluator used to weight the moves of the playouts
+    /// # Note
+    /// * A move is chosen with the probability proportional to exp(score / temperature),
+    ///   where score is the evaluation of the board after the move from the mover's side
+    /// * None chooses moves uniformly at random (default)
+    pub fn set_playout_evaluator(&mut self, evaluator: Option<Arc<dyn Evaluator>>) {
+        self.playout_evaluator = evaluator;
+    }
+
+    /// Get the temperature of the weighted playouts
+    pub fn get_temperature(&self) -> f64 {
+        self.temperature
+    }
+
+    /// Set the temperature of the weighted playouts
+    /// # Note
+    /// * Higher temperature makes the playouts more random
+    /// * If the temperature is not positive, the best move is always chosen
+    pub fn set_temperature(&mut self, temperature: f64) {
+        self.temperature = temperature;
+    }
+
+    /// Get the evaluator used to filter the positions by balance
+    pub fn get_balance_evaluator(&self) -> Option<Arc<dyn Evaluator>> {
+        self.balance_evaluator.clone()
+    }
+
+    /// Set the evaluator used to filter the positions by balance
+    /// # Note
+    /// * A position is accepted if the absolute value of its evaluation is at most `max_imbalance`
+    /// * None accepts all positions (default)
+    pub fn set_balance_evaluator(&mut self, evaluator: Option<Arc<dyn Evaluator>>) {
+        self.balance_evaluator = evaluator;
+    }
+
+    /// Get the maximum absolute evaluation of an accepted position
+    pub fn get_max_imbalance(&self) -> i32 {
+        self.max_imbalance
+    }
+
+    /// Set the maximum absolute evaluation of an accepted position
+    pub fn set_max_imbalance(&mut self, max_imbalance: i32) {
+        self.max_imbalance = max_imbalance;
+    }
+
+    /// Get the maximum number of playouts for one position
+    pub fn get_max_attempts(&self) -> usize {
+        self.max_attempts
+    }
+
+    /// Set the maximum number of playouts for one position
+    pub fn set_max_attempts(&mut self, max_attempts: usize) {
+        self.max_attempts = max_attempts;
+    }
+
+    /// Generate a random position
+    /// # Returns
+    /// * Some(board) with `n_empties` empty squares
+    /// * None if no position is accepted within `max_attempts` playouts
+    /// # Note
+    /// * A playout is retried if the game is over before reaching `n_empties`
+    ///   or the position is rejected by the balance filter
+    pub fn generate(&mut self) -> Option<Board> {
+        if self.n_empties == 0 || self.n_empties > MAX_EMPTIES {
+            return None;
+        }
+        for _ in 0..self.max_attempts {
+            let Some(mut board) = self.play_out() else {
+                continue;
+            };
+            if let Some(evaluator) = &self.balance_evaluator {
+                if evaluator.evaluate(&mut board).abs() > self.max_imbalance {
+                    continue;
+                }
+            }
+            return Some(board);
+        }
+        None
+    }
+
+    /// Generate random positions
+    /// # Arguments
+    /// * `n` - Number of positions
+    /// # Returns
+    /// * List of positions, shorter than `n` if `generate` fails
+    pub fn generate_n(&mut self, n: usize) -> Vec<Board> {
+        self.take(n).collect()
+    }
+
+    fn play_out(&mut self) -> Option<Board> {
+        let mut board = Board::new();
+        loop {
+            if board.is_game_over() {
+                return None;
+            }
+            if board.is_pass() {
+                board.do_pass().unwrap();
+                continue;
+            }
+            if (BOARD_SIZE * BOARD_SIZE) as i32 - board.piece_sum() == self.n_empties as i32 {
+                return Some(board);
+            }
+            let pos = match self.playout_evaluator.clone() {
+                Some(evaluator) => self.choose_weighted_move(&mut board, &evaluator),
+                None => board.get_random_move_with_rng(&mut self.rng).unwrap(),
+            };
+            board.do_move(pos).unwrap();
+        }
+    }
+
+    fn choose_weighted_move(&mut self, board: &mut Board, evaluator: &Arc<dyn Evaluator>) -> usize {
+        let mut scores: StackVec64<(usize, f64)> = StackVec64::new();
+        for (pos, mut child) in board.get_child_boards_iter() {
+            scores.push((pos, -evaluator.evaluate(&mut child) as f64));
+        }
+        let best = scores.iter().fold(
+            (0, f64::NEG_INFINITY),
+            |best, &s| if s.1 > best.1 { s } else { best },
+        );
+        if self.temperature <= 0.0 {
+            return best.0;
+        }
+        let weight = |score: f64| ((score - best.1) / self.temperature).exp();
+        let total: f64 = scores.iter().map(|&(_, score)| weight(score)).sum();
+        let mut r = self.rng.gen::<f64>() * total;
+        for &(pos, score) in scores.iter() {
+            r -= weight(score);
+            if r < 0.0 {
+                return pos;
+            }
+        }
+        best.0
+    }
+}
+
+impl Iterator for PositionGenerator {
+    type Item = Board;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.generate()
+    }
+}
@@ -0,0 +1,118 @@
+use core::fmt;
+use std::time::Duration;
+
+use crate::board::Move;
+
+/// Result of a search with the expected continuation and statistics
+/// # Note
+/// * The score is from the side to move, in the unit of the searcher
+///   (evaluator score for `AlphaBetaSearch`, win rate for `MctsSearch` and `ThunderSearch`)
+/// * The principal variation is empty if the game is over
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchResult {
+    pv: Vec<Move>,
+    score: f64,
+    depth: usize,
+    nodes: u64,
+    elapsed: Duration,
+    interrupted: bool,
+}
+
+impl SearchResult {
+    /// Create a new SearchResult instance
+    /// # Arguments
+    /// * `pv` - Principal variation, starting with the best move
+    /// * `score` - Score of the board
+    /// * `depth` - Depth reached by the search
+    /// * `nodes` - Number of nodes searched
+    /// * `elapsed` - Time spent on the search
+    /// * `interrupted` - If the search was stopped by the timeout
+    pub fn new(
+        pv: Vec<Move>,
+        score: f64,
+        depth: usize,
+        nodes: u64,
+        elapsed: Duration,
+        interrupted: bool,
+    ) -> Self {
+        Self {
+            pv,
+            score,
+            depth,
+            nodes,
+            elapsed,
+            interrupted,
+        }
+    }
+
+    /// Get the principal variation
+    /// # Returns
+    /// * Moves expected to be played from the board, including passes
+    pub fn get_pv(&self) -> &[Move] {
+        &self.pv
+    }
+
+    /// Get the best move
+    /// # Returns
+    /// * Some(pos) for a move, None if the player must pass or the game is over
+    pub fn get_best_move(&self) -> Option<usize> {
+        self.pv.first().and_then(|mv| Option::<usize>::from(*mv))
+    }
+
+    /// Get the best move as a Move
+    /// # Returns
+    /// * Some(Move::Pass) if the player must pass, None if the game is over
+    pub fn get_best_typed_move(&self) -> Option<Move> {
+        self.pv.first().copied()
+    }
+
+    /// Get the score of the board
+    pub fn get_score(&self) -> f64 {
+        self.score
+    }
+
+    /// Get the depth reached by the search
+    /// # Note
+    /// * Number of plies searched for `AlphaBetaSearch`,
+    ///   depth of the deepest expanded node for `MctsSearch` and `ThunderSearch`
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Get the number of nodes searched
+    /// # Note
+    /// * Number of visited positions for `AlphaBetaSearch`,
+    ///   number of playouts for `MctsSearch` and `ThunderSearch`
+    pub fn get_nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// Get the time spent on the search
+    pub fn get_elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Check if the search was stopped by the timeout
+    pub fn is_interrupted(&self) -> bool {
+        self.interrupted
+    }
+}
+
+impl fmt::Display for SearchResult {
+    /// Format as "score: 2, depth: 5, nodes: 1234, time: 1.2ms, pv: f5 d6 c3"
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "score: {}, depth: {}, nodes: {}, time: {:?}, pv:",
+            self.score, self.depth, self.nodes, self.elapsed
+        )?;
+        for mv in &self.pv {
+            write!(f, " {}", mv)?;
+        }
+        if self.interrupted {
+            write!(f, " (interrupted)")?;
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,11 @@
 use crate::board::Board;
+use crate::search::playout_tree::{game_value, new_rng, PlayoutNode};
 use crate::search::time_keeper::TimeKeeper;
-use crate::search::Search;
+use crate::search::{Search, SearchResult};
+use rand::rngs::StdRng;
 use rand::Rng;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use super::winrate_evaluator::WinrateEvaluator;
 
@@ -29,11 +31,13 @@ impl ThunderNode {
     }
 
     fn expand(&mut self) {
-        if let Some(children) = self.board.get_child_boards() {
+        if !self.board.is_pass() {
+            let epsilon = self.epsilon;
+            let evaluator = &self.evaluator;
             self.children = Some(
-                children
-                    .into_iter()
-                    .map(|b| ThunderNode::new(b, self.epsilon, self.evaluator.clone()))
+                self.board
+                    .get_child_boards_iter()
+                    .map(|(_, b)| ThunderNode::new(b, epsilon, evaluator.clone()))
                     .collect(),
             );
         } else {
@@ -49,22 +53,17 @@ impl ThunderNode {
 
     fn score_board(board: &mut Board, evaluator: &Arc<dyn WinrateEvaluator>) -> f64 {
         if board.is_game_over() {
-            match (board.is_win(), board.is_lose()) {
-                (Ok(true), _) => return 1.0,
-                (_, Ok(true)) => return 0.0,
-                _ => return 0.5,
-            }
+            return game_value(board);
         }
         evaluator.evaluate(board)
     }
 
-    fn select_child_index(&self) -> usize {
+    fn select_child_index(&self, rng: &mut StdRng) -> usize {
         for (i, child) in self.children.as_ref().unwrap().iter().enumerate() {
             if child.n_visits == 0 {
                 return i;
             }
         }
-        let mut rng = rand::thread_rng();
         if rng.gen_bool(self.epsilon) {
             return rng.gen_range(0..self.children.as_ref().unwrap().len());
         }
@@ -80,18 +79,9 @@ impl ThunderNode {
         best_child_index
     }
 
-    fn evaluate(&mut self) -> f64 {
+    fn evaluate(&mut self, rng: &mut StdRng) -> f64 {
         if self.board.is_game_over() {
-            let value = match self.board.get_winner().unwrap() {
-                Some(winner) => {
-                    if winner == self.board.get_turn() {
-                        1.0
-                    } else {
-                        0.0
-                    }
-                }
-                None => 0.5,
-            };
+            let value = game_value(&self.board);
             self.w += value;
             self.n_visits += 1;
             value
@@ -102,8 +92,8 @@ impl ThunderNode {
             self.expand();
             value
         } else {
-            let child_index = self.select_child_index();
-            let value = 1.0 - self.children.as_mut().unwrap()[child_index].evaluate();
+            let child_index = self.select_child_index(rng);
+            let value = 1.0 - self.children.as_mut().unwrap()[child_index].evaluate(rng);
             self.w += value;
             self.n_visits += 1;
             value
@@ -111,6 +101,24 @@ impl ThunderNode {
     }
 }
 
+impl PlayoutNode for ThunderNode {
+    fn get_board_mut(&mut self) -> &mut Board {
+        &mut self.board
+    }
+
+    fn get_n_visits(&self) -> usize {
+        self.n_visits
+    }
+
+    fn get_children(&self) -> Option<&[Self]> {
+        self.children.as_deref()
+    }
+
+    fn get_children_mut(&mut self) -> Option<&mut [Self]> {
+        self.children.as_deref_mut()
+    }
+}
+
 #[derive(Debug)]
 pub struct ThunderSearch {
     n_playouts: usize,
@@ -118,12 +126,13 @@ pub struct ThunderSearch {
     evaluator: Arc<dyn WinrateEvaluator>,
     margin_time: f64,
     check_interval: usize,
+    seed: Option<u64>,
 }
 
 impl ThunderSearch {
     /// Create a new ThunderSearch instance.
     /// # Arguments
-    /// * `n_playouts` - The number of playouts to run.
+    /// * `n_playouts` - The number of playouts to run, 0 is treated as 1.
     /// * `evaluator` - The evaluator to evaluate the board.
     /// * `c` - The exploration parameter.
     /// * `expansion_threshold` - The number of visits to expand the node.
@@ -131,11 +140,12 @@ impl ThunderSearch {
     /// A new MctsSearch instance.
     pub fn new(n_playouts: usize, epsilon: f64, evaluator: Arc<dyn WinrateEvaluator>) -> Self {
         Self {
-            n_playouts,
+            n_playouts: n_playouts.max(1),
             epsilon,
             evaluator,
             margin_time: DEFAULT_MARGIN_TIME,
             check_interval: DEFAULT_CHECK_INTERVAL,
+            seed: None,
         }
     }
 
@@ -145,8 +155,10 @@ impl ThunderSearch {
     }
 
     /// Set the number of playouts to run.
+    /// # Note
+    /// * 0 is treated as 1, so that a move is always chosen by at least one playout.
     pub fn set_n_playouts(&mut self, n_playouts: usize) {
-        self.n_playouts = n_playouts;
+        self.n_playouts = n_playouts.max(1);
     }
 
     /// Get the exploration parameter.
@@ -178,6 +190,52 @@ impl ThunderSearch {
     pub fn set_check_interval(&mut self, check_interval: usize) {
         self.check_interval = check_interval;
     }
+
+    /// Get the seed of the random number generator.
+    pub fn get_seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Set the seed of the random number generator.
+    /// # Note
+    /// * With a seed, each search starts from the same random state,
+    ///   so the same board gives the same selections and the same move.
+    /// * Searches with a timeout are reproducible only if they are not stopped by the timeout.
+    /// * None uses a new random state for each search (default).
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+    }
+
+    // Run the playouts until the number of playouts or the timeout is reached.
+    fn run(&self, board: &mut Board, time_keeper: Option<&TimeKeeper>) -> SearchResult {
+        let start = Instant::now();
+        if board.is_game_over() {
+            return SearchResult::new(Vec::new(), game_value(board), 0, 0, start.elapsed(), false);
+        }
+        let mut root = ThunderNode::new(board.clone(), self.epsilon, self.evaluator.clone());
+        root.expand();
+        let mut rng = new_rng(self.seed, 0);
+        let mut n_playouts = 0;
+        let mut interrupted = false;
+        for i in 0..self.n_playouts {
+            root.evaluate(&mut rng);
+            n_playouts += 1;
+            if i % self.check_interval == 0
+                && time_keeper.is_some_and(|time_keeper| time_keeper.is_timeout())
+            {
+                interrupted = n_playouts < self.n_playouts;
+                break;
+            }
+        }
+        SearchResult::new(
+            root.get_pv(),
+            root.w / root.n_visits as f64,
+            root.get_depth(),
+            n_playouts as u64,
+            start.elapsed(),
+            interrupted,
+        )
+    }
 }
 
 const DEFAULT_MARGIN_TIME: f64 = 0.0011;
@@ -191,21 +249,7 @@ impl Search for ThunderSearch {
     /// `Some(usize)` - The best move.
     /// `None` - player must pass.
     fn get_move(&self, board: &mut Board) -> Option<usize> {
-        let mut root = ThunderNode::new(board.clone(), self.epsilon, self.evaluator.clone());
-        root.expand();
-        for _ in 0..self.n_playouts {
-            root.evaluate();
-        }
-        let mut best_child_index = 0;
-        let mut best_n_visits = 0;
-        for (i, child) in root.children.as_ref().unwrap().iter().enumerate() {
-            if child.n_visits > best_n_visits {
-                best_n_visits = child.n_visits;
-                best_child_index = i;
-            }
-        }
-        let legal_moves = board.get_legal_moves_vec();
-        Some(legal_moves[best_child_index])
+        self.search(board).get_best_move()
     }
 
     /// Get the best move for the given board with a timeout.
@@ -220,26 +264,7 @@ impl Search for ThunderSearch {
     /// The search will be stopped when the timeout is reached or the number of playouts is reached.
     /// If you want to stop the search when the timeout is reached, set the timeout to a bigger value.
     fn get_move_with_timeout(&self, board: &mut Board, timeout: Duration) -> Option<usize> {
-        let mut root = ThunderNode::new(board.clone(), self.epsilon, self.evaluator.clone());
-        root.expand();
-        let search_duration = timeout.as_secs_f64() - self.margin_time;
-        let time_keeper = TimeKeeper::new(Duration::from_secs_f64(search_duration));
-        for i in 0..self.n_playouts {
-            root.evaluate();
-            if i % self.check_interval == 0 && time_keeper.is_timeout() {
-                break;
-            }
-        }
-        let mut best_child_index = 0;
-        let mut best_n_visits = 0;
-        for (i, child) in root.children.as_ref().unwrap().iter().enumerate() {
-            if child.n_visits > best_n_visits {
-                best_n_visits = child.n_visits;
-                best_child_index = i;
-            }
-        }
-        let legal_moves = board.get_legal_moves_vec();
-        Some(legal_moves[best_child_index])
+        self.search_with_timeout(board, timeout).get_best_move()
     }
 
     /// Get the search score for the given board.
@@ -251,18 +276,22 @@ impl Search for ThunderSearch {
     /// The search score is the winrate of the best move.
     /// The search will be stopped when the number of playouts is reached.
     fn get_search_score(&self, board: &mut Board) -> f64 {
-        if board.is_game_over() {
-            return match (board.is_win(), board.is_lose()) {
-                (Ok(true), _) => 1.0,
-                (_, Ok(true)) => 0.0,
-                _ => 0.5,
-            };
-        }
-        let mut root = ThunderNode::new(board.clone(), self.epsilon, self.evaluator.clone());
-        root.expand();
-        for _ in 0..self.n_playouts {
-            root.evaluate();
-        }
-        root.w / root.n_visits as f64
+        self.search(board).get_score()
+    }
+
+    /// Search the board with the number of playouts.
+    /// # Note
+    /// * The principal variation follows the most visited children.
+    /// * The score is the winrate of the board, same as `get_search_score`.
+    /// * Nodes are the number of playouts.
+    fn search(&self, board: &mut Board) -> SearchResult {
+        self.run(board, None)
+    }
+
+    /// Search the board with the number of playouts or until the timeout.
+    fn search_with_timeout(&self, board: &mut Board, timeout: Duration) -> SearchResult {
+        let search_duration = timeout.as_secs_f64() - self.margin_time;
+        let time_keeper = TimeKeeper::new(Duration::from_secs_f64(search_duration));
+        self.run(board, Some(&time_keeper))
     }
 }
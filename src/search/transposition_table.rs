@@ -0,0 +1,232 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// Layout of the data word of a slot
+// bits 0..32: score, 32..40: depth, 40..42: bound, 42..49: best move,
+// 49: set in every stored entry, so that an empty slot is not found
+const DEPTH_SHIFT: u32 = 32;
+const BOUND_SHIFT: u32 = 40;
+const MOVE_SHIFT: u32 = 42;
+const VALID: u64 = 1 << 49;
+const MAX_DEPTH: usize = 0xFF;
+const NO_MOVE: u64 = 64;
+
+/// Kind of a score stored in the transposition table
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Bound {
+    /// The score is the exact value of the position
+    Exact,
+    /// The value is at least the score (beta cutoff)
+    Lower,
+    /// The value is at most the score (no move raised alpha)
+    Upper,
+}
+
+impl Bound {
+    /// Kind of a score returned by a search with the window (alpha, beta)
+    pub(crate) fn from_window(score: i32, alpha: i32, beta: i32) -> Self {
+        if score <= alpha {
+            Bound::Upper
+        } else if score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        }
+    }
+}
+
+/// Result of a search of a position
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct TTEntry {
+    pub(crate) score: i32,
+    pub(crate) depth: usize,
+    pub(crate) bound: Bound,
+    pub(crate) best_move: Option<usize>,
+}
+
+impl TTEntry {
+    fn pack(&self) -> u64 {
+        let bound = match self.bound {
+            Bound::Exact => 0,
+            Bound::Lower => 1,
+            Bound::Upper => 2,
+        };
+        let best_move = self.best_move.map_or(NO_MOVE, |m| m as u64);
+        (self.score as u32 as u64)
+            | ((self.depth.min(MAX_DEPTH) as u64) << DEPTH_SHIFT)
+            | (bound << BOUND_SHIFT)
+            | (best_move << MOVE_SHIFT)
+            | VALID
+    }
+
+    fn unpack(data: u64) -> Self {
+        let bound = match (data >> BOUND_SHIFT) & 0x3 {
+            0 => Bound::Exact,
+            1 => Bound::Lower,
+            _ => Bound::Upper,
+        };
+        let best_move = (data >> MOVE_SHIFT) & 0x7F;
+        Self {
+            score: data as u32 as i32,
+            depth: ((data >> DEPTH_SHIFT) & 0xFF) as usize,
+            bound,
+            best_move: (best_move != NO_MOVE).then_some(best_move as usize),
+        }
+    }
+}
+
+/// Statistics of the transposition table during a search
+/// # Note
+/// * A probe is a lookup of a position, and a hit is a probe that found the position
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct TTStats {
+    probes: u64,
+    hits: u64,
+    stores: u64,
+}
+
+impl TTStats {
+    /// Get the number of lookups
+    pub fn get_probes(&self) -> u64 {
+        self.probes
+    }
+
+    /// Get the number of lookups that found the position
+    pub fn get_hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Get the number of stored results
+    pub fn get_stores(&self) -> u64 {
+        self.stores
+    }
+
+    /// Get the ratio of hits to probes
+    /// # Returns
+    /// * Hit rate (0.0..=1.0), 0.0 if there was no probe
+    pub fn get_hit_rate(&self) -> f64 {
+        if self.probes == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / self.probes as f64
+    }
+}
+
+// Each slot stores the hash xor-ed with the data, so a slot torn by
+// concurrent writes is detected as a miss instead of returning wrong data.
+#[derive(Default)]
+struct Slot {
+    key: AtomicU64,
+    data: AtomicU64,
+}
+
+/// Number of entries of a table of the requested size
+/// # Returns
+/// * The size rounded down to a power of two, or 0
+pub(crate) fn table_size(size: usize) -> usize {
+    if size == 0 {
+        0
+    } else {
+        1 << size.ilog2()
+    }
+}
+
+/// Fixed-size hash table of search results, shared without locks
+/// # Note
+/// * A table is used by the threads of a single search only
+/// * Statistics are counted by each thread and added once at the end of its search
+pub(crate) struct TranspositionTable {
+    slots: Box<[Slot]>,
+    probes: AtomicU64,
+    hits: AtomicU64,
+    stores: AtomicU64,
+}
+
+impl TranspositionTable {
+    /// Create a new table
+    /// # Arguments
+    /// * `size` - Number of entries, rounded down to a power of two (0 disables the table)
+    pub(crate) fn new(size: usize) -> Self {
+        Self {
+            slots: (0..table_size(size)).map(|_| Slot::default()).collect(),
+            probes: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            stores: AtomicU64::new(0),
+        }
+    }
+
+    /// Get the number of entries
+    pub(crate) fn get_size(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Add the statistics counted by a thread
+    pub(crate) fn add_stats(&self, stats: &TTStats) {
+        self.probes.fetch_add(stats.probes, Ordering::Relaxed);
+        self.hits.fetch_add(stats.hits, Ordering::Relaxed);
+        self.stores.fetch_add(stats.stores, Ordering::Relaxed);
+    }
+
+    /// Get the statistics added by the threads
+    pub(crate) fn get_stats(&self) -> TTStats {
+        TTStats {
+            probes: self.probes.load(Ordering::Relaxed),
+            hits: self.hits.load(Ordering::Relaxed),
+            stores: self.stores.load(Ordering::Relaxed),
+        }
+    }
+
+    fn slot(&self, hash: u64) -> &Slot {
+        &self.slots[hash as usize & (self.slots.len() - 1)]
+    }
+
+    fn load(&self, hash: u64) -> Option<u64> {
+        let slot = self.slot(hash);
+        let data = slot.data.load(Ordering::Relaxed);
+        let key = slot.key.load(Ordering::Relaxed);
+        (key ^ data == hash && data & VALID != 0).then_some(data)
+    }
+
+    /// Look up a position
+    /// # Arguments
+    /// * `stats` - Statistics of the calling thread
+    pub(crate) fn probe(&self, hash: u64, stats: &mut TTStats) -> Option<TTEntry> {
+        if self.slots.is_empty() {
+            return None;
+        }
+        stats.probes += 1;
+        let data = self.load(hash)?;
+        stats.hits += 1;
+        Some(TTEntry::unpack(data))
+    }
+
+    /// Store the result of a search
+    /// # Arguments
+    /// * `stats` - Statistics of the calling thread
+    /// # Note
+    /// * A result of the same position searched deeper is kept
+    pub(crate) fn store(&self, hash: u64, entry: TTEntry, stats: &mut TTStats) {
+        if self.slots.is_empty() {
+            return;
+        }
+        if let Some(data) = self.load(hash) {
+            if TTEntry::unpack(data).depth > entry.depth {
+                return;
+            }
+        }
+        let data = entry.pack();
+        let slot = self.slot(hash);
+        slot.data.store(data, Ordering::Relaxed);
+        slot.key.store(hash ^ data, Ordering::Relaxed);
+        stats.stores += 1;
+    }
+}
+
+impl fmt::Debug for TranspositionTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TranspositionTable")
+            .field("size", &self.get_size())
+            .field("stats", &self.get_stats())
+            .finish()
+    }
+}
@@ -0,0 +1,53 @@
+use std::iter::FusedIterator;
+
+/// Iterator over the positions of the set bits of a bitboard
+/// # Example
+/// ```
+/// use rust_reversi_core::utils::BitIter;
+/// let positions: Vec<usize> = BitIter::new(0x8000000000000001).collect();
+/// assert_eq!(positions, vec![0, 63]);
+/// ```
+/// # Note
+/// * Positions are yielded in ascending order, same as `Board::get_legal_moves_vec`
+/// * The iterator only keeps the remaining bits, so it never allocates
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BitIter {
+    bits: u64,
+}
+
+impl BitIter {
+    #[inline]
+    pub fn new(bits: u64) -> Self {
+        Self { bits }
+    }
+
+    /// Get the bits not yet yielded
+    #[inline]
+    pub fn get_bits(&self) -> u64 {
+        self.bits
+    }
+}
+
+impl Iterator for BitIter {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bits == 0 {
+            return None;
+        }
+        let lz = self.bits.leading_zeros();
+        self.bits &= !(1u64 << (63 - lz));
+        Some(lz as usize)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.bits.count_ones() as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for BitIter {}
+
+impl FusedIterator for BitIter {}
@@ -1,2 +1,4 @@
+mod bit_iter;
 mod stack_vec;
+pub use bit_iter::BitIter;
 pub use stack_vec::StackVec64;
@@ -1,6 +1,8 @@
 use rust_reversi_core::arena::LocalArena;
 use rust_reversi_core::arena::*;
 use rust_reversi_core::arena::{NetworkArenaClient, NetworkArenaServer};
+use rust_reversi_core::board::{Board, Turn};
+use rust_reversi_core::formats::{format_ggf, parse_ggf, parse_ggf_games, GgfResultType};
 use std::thread;
 use std::time::Duration;
 
@@ -54,6 +56,44 @@ mod tests {
         assert!(matches!(result, Err(ArenaError::GameNumberInvalid)));
     }
 
+    #[test]
+    fn arena_openings() {
+        compile_player("random_player");
+        let random_player = get_player_path("random_player");
+
+        let command1 = vec![random_player.to_str().unwrap().to_string()];
+        let command2 = command1.clone();
+
+        let openings = vec![
+            Board::from_transcript("f5d6c3").unwrap(),
+            Board::from_transcript("f5f6e6f4").unwrap(),
+            Board::from_transcript("f5f4e3f6d3").unwrap(),
+        ];
+        let mut arena = LocalArena::new(command1, command2, false);
+        let results = arena.play_openings(&openings).unwrap();
+        assert_eq!(results.len(), openings.len());
+        for result in results.iter() {
+            let (wins1, wins2, draws) = result.get_stats();
+            assert_eq!(wins1 + wins2 + draws, 2);
+        }
+
+        let (wins1, wins2, draws) = arena.get_stats();
+        assert_eq!(wins1 + wins2 + draws, openings.len() * 2);
+
+        let results = arena.play_openings(&[]).unwrap();
+        assert!(results.is_empty());
+
+        // results are of each call only
+        let results = arena.play_transcripts(&["f5d6", "f5f6"]).unwrap();
+        assert_eq!(results.len(), 2);
+        for result in results.iter() {
+            let (wins1, wins2, draws) = result.get_stats();
+            assert_eq!(wins1 + wins2 + draws, 2);
+        }
+        let result = arena.play_transcripts(&["f5d6", "a1"]);
+        assert!(matches!(result, Err(ArenaError::InvalidOpening)));
+    }
+
     #[test]
     fn arena_invalid_player() {
         compile_player("random_player");
@@ -102,6 +142,125 @@ mod tests {
         let mut arena = LocalArena::new(command1, command2, false);
         let result = arena.play_n(2);
         assert!(matches!(result, Err(ArenaError::GameError(_))));
+
+        // the games of a failed call are only kept as records
+        assert_eq!(arena.get_stats(), (0, 0, 0));
+        assert_eq!(arena.get_pieces(), (0, 0));
+        let records = arena.get_records();
+        assert!(!records.is_empty());
+        assert!(records
+            .iter()
+            .any(|record| matches!(record.get_termination(), Termination::Timeout(_))));
+    }
+
+    #[test]
+    fn arena_records() {
+        compile_player("random_player");
+        let random_player = get_player_path("random_player");
+
+        let command1 = vec![random_player.to_str().unwrap().to_string()];
+        let command2 = command1.clone();
+
+        let mut arena = LocalArena::new(command1.clone(), command2, false);
+        arena.play_n(4).unwrap();
+        arena.play_transcripts(&["f5d6c3"]).unwrap();
+
+        let records = arena.get_records();
+        assert_eq!(records.len(), 6);
+        for record in records.iter() {
+            assert_eq!(record.get_termination(), Termination::GameOver);
+            assert_eq!(record.get_black_player(), command1[0]);
+            let mut board = record.get_initial_board();
+            board
+                .play_transcript(&record.get_transcript().unwrap())
+                .unwrap();
+            assert!(board.is_game_over());
+            let score = (
+                board.black_piece_num() as usize,
+                board.white_piece_num() as usize,
+            );
+            assert_eq!(record.get_score(), score);
+            assert_eq!(record.get_winner(), board.get_winner().unwrap());
+        }
+        let (wins1, wins2, draws) = arena.get_stats();
+        assert_eq!(wins1 + wins2 + draws, records.len());
+
+        let path = std::env::temp_dir().join("rust_reversi_core_arena_records.txt");
+        arena.write_records(&path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(text.lines().count(), 6);
+        let games = parse_ggf_games(&text).unwrap();
+        assert_eq!(games.len(), 6);
+        for (game, record) in games.iter().zip(records.iter()) {
+            assert_eq!(game.get_black_player(), Some(record.get_black_player()));
+            assert_eq!(game.get_white_player(), Some(record.get_white_player()));
+            assert!(game.get_initial_board() == record.get_initial_board());
+            assert_eq!(game.get_moves(), record.get_moves());
+            let (black, white) = record.get_score();
+            let result = game.get_result().unwrap();
+            assert_eq!(result.get_score(), black as f64 - white as f64);
+            assert_eq!(result.get_result_type(), GgfResultType::Normal);
+            assert_eq!(&GameRecord::from_ggf(game).unwrap(), record);
+        }
+    }
+
+    #[test]
+    fn record_ggf_round_trip() {
+        let terminations = [
+            Termination::GameOver,
+            Termination::InvalidMove(Turn::Black),
+            Termination::InvalidMove(Turn::White),
+            Termination::Timeout(Turn::Black),
+            Termination::Crash(Turn::White),
+            Termination::UnexpectedError,
+        ];
+        for termination in terminations {
+            // the network arena names players by their addresses
+            let mut game = parse_ggf("(;GM[Othello]B[F5]W[D6];)").unwrap();
+            game.set_black_player(Some("[::1]:54321".to_string()));
+            game.set_white_player(Some("./player ]".to_string()));
+            game.set_property("C", &termination.to_string()).unwrap();
+            let record = GameRecord::from_ggf(&game).unwrap();
+            assert_eq!(record.get_termination(), termination);
+            assert_eq!(record.get_black_player(), "[::1]:54321");
+
+            let s = format_ggf(&record.to_ggf().unwrap()).unwrap();
+            assert_eq!(
+                GameRecord::from_ggf(&parse_ggf(&s).unwrap()).unwrap(),
+                record
+            );
+        }
+
+        // the termination is needed, RE does not tell a crash from an invalid move
+        let game = parse_ggf("(;GM[Othello]C[black eaten]PB[a]PW[b];)").unwrap();
+        assert!(GameRecord::from_ggf(&game).is_err());
+        let game = parse_ggf("(;GM[Othello]PB[a]PW[b];)").unwrap();
+        assert!(GameRecord::from_ggf(&game).is_err());
+    }
+
+    #[test]
+    fn arena_notation_player() {
+        compile_player("random_player");
+        compile_player("notation_player");
+        let random_player = get_player_path("random_player");
+        let notation_player = get_player_path("notation_player");
+
+        let command1 = vec![notation_player.to_str().unwrap().to_string()];
+        let command2 = vec![random_player.to_str().unwrap().to_string()];
+
+        let mut arena = LocalArena::new(command1, command2, false);
+        arena.play_n(10).unwrap();
+
+        let (wins1, wins2, draws) = arena.get_stats();
+        assert_eq!(wins1 + wins2 + draws, 10);
+        for record in arena.get_records().iter() {
+            let mut board = record.get_initial_board();
+            for mv in record.get_typed_moves().unwrap() {
+                board.play_move(mv).unwrap();
+            }
+            assert!(board.is_game_over());
+        }
     }
 
     #[test]
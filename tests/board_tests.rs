@@ -1,6 +1,14 @@
 #[cfg(test)]
 mod tests {
-    use rust_reversi_core::board::{Board, BoardError, Color, Turn};
+    use std::collections::HashSet;
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use rust_reversi_core::board::{
+        format_square, format_transcript, parse_move, parse_square, Board, BoardError, Color,
+        GameHistory, Move, PerftMode, RenderOptions, Square, Transform, Turn,
+    };
 
     #[test]
     fn new_board() {
@@ -152,6 +160,517 @@ mod tests {
         assert_eq!(turn, cloned_turn);
     }
 
+    #[test]
+    fn square_notation() {
+        for pos in 0..64 {
+            let square = format_square(pos).unwrap();
+            assert_eq!(parse_square(&square).unwrap(), pos);
+        }
+        assert_eq!(parse_square("h8").unwrap(), 63);
+        assert_eq!(parse_move("pass").unwrap(), None);
+        assert!(matches!(
+            format_square(64),
+            Err(BoardError::InvalidPosition)
+        ));
+        assert!(matches!(
+            parse_square("a9"),
+            Err(BoardError::InvalidNotation)
+        ));
+        assert!(matches!(
+            parse_square("f55"),
+            Err(BoardError::InvalidNotation)
+        ));
+        assert!(matches!(parse_move("xx"), Err(BoardError::InvalidNotation)));
+    }
+
+    #[test]
+    fn square_and_move() -> Result<(), BoardError> {
+        for pos in 0..64 {
+            let square = Square::new(pos)?;
+            assert_eq!(square.get_index(), pos);
+            assert_eq!(square.get_row() * 8 + square.get_col(), pos);
+            assert_eq!(Square::from_bit(square.get_bit())?, square);
+            assert_eq!(square.to_string(), format_square(pos)?);
+            assert_eq!(square.to_string().parse::<Square>()?, square);
+        }
+        assert!(matches!(Square::new(64), Err(BoardError::InvalidPosition)));
+        assert!(matches!(
+            Square::from_row_col(0, 8),
+            Err(BoardError::InvalidPosition)
+        ));
+        assert!(matches!(
+            Square::from_bit(0b11),
+            Err(BoardError::InvalidPosition)
+        ));
+
+        let f5 = Square::from_row_col(4, 5)?;
+        assert_eq!("F5".parse::<Move>()?, Move::Place(f5));
+        assert_eq!("pass".parse::<Move>()?, Move::Pass);
+        assert_eq!(Move::Pass.to_string(), "PA");
+        assert_eq!(Option::<usize>::from(Move::from(f5)), Some(37));
+        assert_eq!(Move::try_from(None)?, Move::Pass);
+        assert!(Move::try_from(Some(64)).is_err());
+
+        // black must pass after c1
+        let mut board = Board::from_transcript("d3c3b3b2f5a3a1c1")?;
+        assert!(board.get_legal_squares().is_empty());
+        assert!(matches!(
+            board.play_move(Move::Place(f5)),
+            Err(BoardError::InvalidMove)
+        ));
+        board.play_move(Move::Pass)?;
+        let e3 = "e3".parse::<Square>()?;
+        assert!(board.is_legal_square(e3));
+        let legal_squares: Vec<usize> = board
+            .get_legal_squares()
+            .into_iter()
+            .map(usize::from)
+            .collect();
+        let legal_moves: Vec<usize> = board.get_legal_moves_vec().iter().copied().collect();
+        assert_eq!(legal_squares, legal_moves);
+
+        let before = board.get_board();
+        let record = board.play_move_undoable(Move::Place(e3))?;
+        board.undo_move(&record);
+        assert_eq!(board.get_board(), before);
+        Ok(())
+    }
+
+    #[test]
+    fn play_transcript() -> Result<(), BoardError> {
+        let mut board = Board::new();
+        board.do_move_str("f5")?;
+        board.do_move_str("d6")?;
+        assert_eq!(
+            board.get_board(),
+            Board::from_transcript("f5d6")?.get_board()
+        );
+
+        // black must pass after c1, the pass may be omitted
+        let mut board = Board::new();
+        let moves = board.play_transcript("d3c3b3b2f5a3a1c1e3")?;
+        assert_eq!(format_transcript(&moves)?, "d3c3b3b2f5a3a1c1PAe3");
+        assert_eq!(board.get_turn(), Turn::Black);
+        assert_eq!(board.piece_sum(), 13);
+
+        // illegal and malformed transcripts leave the board unchanged
+        let mut board = Board::new();
+        assert!(matches!(
+            board.play_transcript("f5f5"),
+            Err(BoardError::InvalidMove)
+        ));
+        assert!(matches!(
+            board.play_transcript("f5d"),
+            Err(BoardError::InvalidNotation)
+        ));
+        assert_eq!(board.get_board(), Board::new().get_board());
+        Ok(())
+    }
+
+    #[test]
+    fn legal_moves_iter() {
+        let mut board = Board::new();
+        while !board.is_game_over() {
+            let legal_moves: Vec<usize> = board.get_legal_moves_iter().collect();
+            assert_eq!(legal_moves, board.get_legal_moves_vec().to_vec());
+            assert_eq!(board.get_legal_moves_iter().len(), legal_moves.len());
+
+            let children: Vec<(usize, Board)> = board.get_child_boards_iter().collect();
+            match board.get_child_boards() {
+                Some(child_boards) => {
+                    assert_eq!(children.len(), child_boards.len());
+                    for ((pos, child), expected) in children.iter().zip(child_boards.iter()) {
+                        let mut moved = board.clone();
+                        moved.do_move(*pos).unwrap();
+                        assert!(*child == moved);
+                        assert!(child == expected);
+                        assert_eq!(child.get_hash(), moved.get_hash());
+                    }
+                }
+                None => assert!(children.is_empty()),
+            }
+
+            if board.is_pass() {
+                board.do_pass().unwrap();
+            } else {
+                let pos = board.get_random_move().unwrap();
+                board.do_move(pos).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn simd_matches_scalar() {
+        fn check(board: &mut Board) {
+            let (player_board, opponent_board, _) = board.get_board();
+            assert_eq!(board.get_legal_moves(), board.get_legal_moves_scalar());
+            let mut empty = !(player_board | opponent_board);
+            while empty != 0 {
+                let bit = empty & empty.wrapping_neg();
+                empty ^= bit;
+                assert_eq!(board.get_flips_bit(bit), board.get_flips_bit_scalar(bit));
+            }
+        }
+
+        for _ in 0..100 {
+            let mut board = Board::new();
+            while !board.is_game_over() {
+                check(&mut board);
+                if board.is_pass() {
+                    board.do_pass().unwrap();
+                } else {
+                    let pos = board.get_random_move().unwrap();
+                    board.do_move(pos).unwrap();
+                }
+            }
+        }
+
+        // arbitrary positions, not necessarily reachable
+        let mut x: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut next = || {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            x
+        };
+        for _ in 0..10000 {
+            let occupied = next() | next();
+            let player_board = occupied & next();
+            let opponent_board = occupied & !player_board;
+            let mut board = Board::new();
+            board.set_board(player_board, opponent_board, Turn::Black);
+            check(&mut board);
+        }
+    }
+
+    #[test]
+    fn render_and_parse() {
+        let mut options = RenderOptions::new();
+        options.set_legal_moves(true);
+        options.set_disc_count(true);
+        options.set_turn(true);
+
+        let mut board = Board::new();
+        let mut last_move = None;
+        while !board.is_game_over() {
+            // the grid of `Display` does not tell the side to move
+            let parsed: Board = board.to_string().parse().unwrap();
+            assert_eq!(
+                parsed.get_board_line().unwrap(),
+                board.get_board_line().unwrap()
+            );
+            assert_eq!(parsed.get_turn(), Turn::Black);
+            assert_eq!(board.to_string(), board.render(&RenderOptions::new()));
+
+            options.set_last_move(last_move);
+            let parsed: Board = board.render(&options).parse().unwrap();
+            assert!(parsed == board);
+            options.set_ansi(true);
+            let rendered = board.render(&options);
+            assert!(rendered.contains('\x1b'));
+            let parsed: Board = rendered.parse().unwrap();
+            assert!(parsed == board);
+            options.set_ansi(false);
+
+            let turn_char = match board.get_turn() {
+                Turn::Black => 'X',
+                Turn::White => 'O',
+            };
+            let line = format!("{} {}", board.get_board_line().unwrap(), turn_char);
+            let parsed: Board = line.parse().unwrap();
+            assert!(parsed == board);
+
+            if board.is_pass() {
+                board.do_pass().unwrap();
+                last_move = None;
+            } else {
+                let pos = board.get_random_move().unwrap();
+                board.do_move(pos).unwrap();
+                last_move = Some(Square::new(pos).unwrap());
+            }
+        }
+
+        // without the side to move, black is to move
+        let parsed: Board = Board::new().get_board_line().unwrap().parse().unwrap();
+        assert_eq!(parsed.get_turn(), Turn::Black);
+        let grid = Board::new().render(&RenderOptions::new());
+        assert!(!grid.contains("to move"));
+        assert!(grid.parse::<Board>().unwrap() == Board::new());
+
+        assert!(matches!(
+            "-".repeat(63).parse::<Board>(),
+            Err(BoardError::InvalidFormat)
+        ));
+        assert!(matches!(
+            format!("{}?", "-".repeat(63)).parse::<Board>(),
+            Err(BoardError::InvalidCharactor)
+        ));
+        assert!(matches!(
+            format!("{} Z", "-".repeat(64)).parse::<Board>(),
+            Err(BoardError::InvalidFormat)
+        ));
+        let missing_row = grid.replace("8|--------\n", "");
+        assert!(matches!(
+            missing_row.parse::<Board>(),
+            Err(BoardError::InvalidFormat)
+        ));
+
+        // an inconsistent board is rendered without panicking
+        let mut board = Board::new();
+        board.set_board(1, 1, Turn::Black);
+        assert!(board.to_string().contains('#'));
+    }
+
+    #[test]
+    fn random_move_with_rng() {
+        let play = |seed: u64| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut board = Board::new();
+            let mut moves = Vec::new();
+            while !board.is_game_over() {
+                if board.is_pass() {
+                    board.do_pass().unwrap();
+                    moves.push(None);
+                } else {
+                    let pos = board.get_random_move_with_rng(&mut rng).unwrap();
+                    board.do_move(pos).unwrap();
+                    moves.push(Some(pos));
+                }
+            }
+            moves
+        };
+        assert_eq!(play(1), play(1));
+        assert_ne!(play(1), play(2));
+
+        let mut board = Board::from_transcript("d3c3b3b2f5a3a1c1").unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(matches!(
+            board.get_random_move_with_rng(&mut rng),
+            Err(BoardError::NoLegalMove)
+        ));
+    }
+
+    #[test]
+    fn undo_move() {
+        for _ in 0..100 {
+            let mut board = Board::new();
+            let mut boards = vec![board.get_board()];
+            let mut records = Vec::new();
+            while !board.is_game_over() {
+                let record = if board.is_pass() {
+                    board.do_pass_undoable().unwrap()
+                } else {
+                    let m = board.get_random_move().unwrap();
+                    board.do_move_undoable(m).unwrap()
+                };
+                records.push(record);
+                boards.push(board.get_board());
+            }
+            boards.pop();
+            while let Some(record) = records.pop() {
+                board.undo_move(&record);
+                assert_eq!(board.get_board(), boards.pop().unwrap());
+            }
+            assert_eq!(board.get_board(), Board::new().get_board());
+        }
+    }
+
+    #[test]
+    fn game_history() -> Result<(), BoardError> {
+        let mut history = GameHistory::new();
+        assert!(matches!(history.undo(), Err(BoardError::NothingToUndo)));
+        for mv in ["d3", "c3", "b3", "b2", "f5", "a3", "a1", "c1"] {
+            history.do_move(parse_square(mv)?)?;
+        }
+        history.do_pass()?;
+        history.do_move(parse_square("e3")?)?;
+        let end = history.get_board().get_board();
+        assert_eq!(history.get_transcript()?, "d3c3b3b2f5a3a1c1PAe3");
+
+        assert_eq!(history.undo()?, Some(parse_square("e3")?));
+        assert_eq!(history.undo()?, None);
+        assert_eq!(history.get_ply(), 8);
+        assert!(history.can_redo());
+        assert_eq!(history.redo()?, None);
+        assert_eq!(history.redo()?, Some(parse_square("e3")?));
+        assert!(matches!(history.redo(), Err(BoardError::NothingToRedo)));
+        assert_eq!(history.get_board().get_board(), end);
+
+        while history.can_undo() {
+            history.undo()?;
+        }
+        assert_eq!(history.get_board().get_board(), Board::new().get_board());
+        history.do_move(parse_square("f5")?)?;
+        assert!(!history.can_redo());
+
+        // the pass is inserted
+        let history = GameHistory::from_transcript("d3c3b3b2f5a3a1c1e3")?;
+        assert_eq!(history.get_transcript()?, "d3c3b3b2f5a3a1c1PAe3");
+        assert_eq!(history.get_board().get_board(), end);
+        assert!(matches!(
+            GameHistory::from_transcript("f5a1"),
+            Err(BoardError::InvalidMove)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn position_hash() {
+        for _ in 0..100 {
+            let mut board = Board::new();
+            let mut records = Vec::new();
+            let mut hashes = vec![board.get_hash()];
+            while !board.is_game_over() {
+                let record = if board.is_pass() {
+                    board.do_pass_undoable().unwrap()
+                } else {
+                    let m = board.get_random_move().unwrap();
+                    board.do_move_undoable(m).unwrap()
+                };
+                records.push(record);
+                let (player_board, opponent_board, turn) = board.get_board();
+                let mut fresh = Board::new();
+                fresh.set_board(player_board, opponent_board, turn);
+                assert_eq!(board.get_hash(), fresh.get_hash());
+                hashes.push(board.get_hash());
+            }
+            while let Some(record) = records.pop() {
+                hashes.pop();
+                board.undo_move(&record);
+                assert_eq!(board.get_hash(), *hashes.last().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn position_eq_ignores_cache() {
+        let mut board1 = Board::from_transcript("f5d6c3").unwrap();
+        let board2 = Board::from_transcript("f5d6c3").unwrap();
+        board1.get_legal_moves();
+        assert!(board1 == board2);
+
+        let mut positions = HashSet::new();
+        positions.insert(board1);
+        assert!(positions.contains(&board2));
+
+        // same stones with the other side to move is another position
+        let (player_board, opponent_board, turn) = board2.get_board();
+        let mut board3 = Board::new();
+        board3.set_board(player_board, opponent_board, turn.opposite());
+        assert!(board3 != board2);
+        assert_ne!(board3.get_hash(), board2.get_hash());
+    }
+
+    #[test]
+    fn transform_bits_and_pos() {
+        for transform in Transform::ALL {
+            for pos in 0..64 {
+                let bit = 1u64 << (63 - pos);
+                let new_pos = transform.apply_pos(pos).unwrap();
+                assert_eq!(transform.apply_bits(bit), 1u64 << (63 - new_pos));
+                assert_eq!(transform.inverse().apply_pos(new_pos).unwrap(), pos);
+            }
+        }
+        assert!(matches!(
+            Transform::Rotate90.apply_pos(64),
+            Err(BoardError::InvalidPosition)
+        ));
+    }
+
+    #[test]
+    fn transform_board() {
+        let mut board = Board::from_transcript("f5d6c3d3c4f4").unwrap();
+        let legal_moves = board.get_legal_moves();
+        let (canonical, _) = board.get_canonical();
+        for transform in Transform::ALL {
+            let mut transformed = board.get_transformed(transform);
+            assert_eq!(
+                transformed.get_legal_moves(),
+                transform.apply_bits(legal_moves)
+            );
+            assert_eq!(transformed.get_turn(), board.get_turn());
+            let (other_canonical, other_transform) = transformed.get_canonical();
+            assert!(other_canonical == canonical);
+            assert!(transformed.get_transformed(other_transform) == canonical);
+        }
+    }
+
+    #[test]
+    fn get_flips() {
+        for _ in 0..100 {
+            let mut board = Board::new();
+            while !board.is_game_over() {
+                if board.is_pass() {
+                    board.do_pass().unwrap();
+                    continue;
+                }
+                let legal_moves = board.get_legal_moves();
+                for pos in 0..64 {
+                    let is_legal = legal_moves & (1u64 << (63 - pos)) != 0;
+                    assert_eq!(board.get_flips(pos).is_ok(), is_legal);
+                }
+                let before = board.get_board();
+                let m = board.get_random_move().unwrap();
+                let flips = board.get_flips(m).unwrap();
+                assert_eq!(board.get_board(), before);
+                let record = board.do_move_undoable(m).unwrap();
+                assert_eq!(record.get_flipped(), flips);
+                let (player_board, _, _) = board.get_board();
+                assert_eq!(player_board & flips, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn stable_stones() {
+        let board = Board::new();
+        assert_eq!(board.get_stable_stones(), (0, 0));
+
+        let mut board = Board::new();
+        board
+            .set_board_str(
+                "XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXOOOOOOOOOOOOOOOOOOOOOOOOOOOOOOXXXX",
+                Turn::Black,
+            )
+            .unwrap();
+        let (player_board, opponent_board, _) = board.get_board();
+        assert_eq!(board.get_stable_stones(), (player_board, opponent_board));
+
+        // stable stones never change color until the end of the game
+        let black_white = |board: &Board| {
+            let (player_board, opponent_board, turn) = board.get_board();
+            match turn {
+                Turn::Black => (player_board, opponent_board),
+                Turn::White => (opponent_board, player_board),
+            }
+        };
+        for _ in 0..100 {
+            let mut board = Board::new();
+            let mut stable_black = 0;
+            let mut stable_white = 0;
+            while !board.is_game_over() {
+                if board.is_pass() {
+                    board.do_pass().unwrap();
+                } else {
+                    let m = board.get_random_move().unwrap();
+                    board.do_move(m).unwrap();
+                }
+                let (black, white) = black_white(&board);
+                assert_eq!(black & stable_black, stable_black);
+                assert_eq!(white & stable_white, stable_white);
+                let (player_stable, opponent_stable) = board.get_stable_stones();
+                let (new_black, new_white) = match board.get_turn() {
+                    Turn::Black => (player_stable, opponent_stable),
+                    Turn::White => (opponent_stable, player_stable),
+                };
+                assert_eq!(new_black & stable_black, stable_black);
+                assert_eq!(new_white & stable_white, stable_white);
+                stable_black = new_black;
+                stable_white = new_white;
+            }
+        }
+    }
+
     const PERFT_MODE1: [u64; 11] = [
         1, 4, 12, 56, 244, 1396, 8200, 55092, 390216, 3005288, 24571284,
     ];
@@ -201,6 +720,7 @@ mod tests {
         let mut board = Board::new();
         for (depth, &nodes) in PERFT_MODE1.iter().enumerate() {
             assert_eq!(perft1(&mut board, depth as u8), nodes);
+            assert_eq!(board.perft(depth, PerftMode::PassIsPly), nodes);
         }
     }
 
@@ -209,6 +729,99 @@ mod tests {
         let mut board = Board::new();
         for (depth, &nodes) in PERFT_MODE2.iter().enumerate() {
             assert_eq!(perft2(&mut board, depth as u8), nodes);
+            assert_eq!(board.perft(depth, PerftMode::PassIsFree), nodes);
+        }
+    }
+
+    #[test]
+    fn perft_divide() {
+        let board = Board::new();
+        for mode in [PerftMode::PassIsPly, PerftMode::PassIsFree] {
+            let divide = board.perft_divide(6, mode);
+            assert_eq!(divide.len(), 4);
+            let total: u64 = divide.iter().map(|&(_, count)| count).sum();
+            assert_eq!(total, board.perft(6, mode));
+            // the initial position is symmetric
+            assert!(divide.iter().all(|&(_, count)| count == total / 4));
+        }
+        assert!(board.perft_divide(0, PerftMode::PassIsPly).is_empty());
+
+        // black must pass
+        let board = Board::from_transcript("d3c3b3b2f5a3a1c1").unwrap();
+        let divide = board.perft_divide(1, PerftMode::PassIsPly);
+        assert_eq!(divide, vec![(None, 1)]);
+        let divide = board.perft_divide(1, PerftMode::PassIsFree);
+        assert_eq!(divide, vec![(None, 2)]);
+    }
+
+    #[test]
+    fn validate_board() {
+        let mut board = Board::new();
+        assert!(board.validate().is_ok());
+        while !board.is_game_over() {
+            if board.is_pass() {
+                board.do_pass().unwrap();
+            } else {
+                let pos = board.get_random_move().unwrap();
+                board.do_move(pos).unwrap();
+            }
+            assert!(board.validate().is_ok());
+        }
+
+        let (player_board, opponent_board, turn) = Board::new().get_board();
+        let board = Board::from_bitboards(player_board, opponent_board, turn).unwrap();
+        assert!(board == Board::new());
+        assert!(matches!(
+            Board::from_bitboards(player_board | opponent_board, opponent_board, turn),
+            Err(BoardError::OverlappingStones)
+        ));
+
+        let mut board = Board::new();
+        board
+            .set_board_str(
+                format!(
+                    "{}{}{}{}{}{}{}{}",
+                    "--------",
+                    "--------",
+                    "--------",
+                    "---XO---",
+                    "---O----",
+                    "--------",
+                    "--------",
+                    "--------",
+                )
+                .as_str(),
+                Turn::Black,
+            )
+            .unwrap();
+        assert!(matches!(board.validate(), Err(BoardError::EmptyCenter)));
+
+        // a1 and a8 are not connected to the center
+        let (player_board, opponent_board, turn) = Board::new().get_board();
+        let a1 = 1 << 63;
+        let h1 = 1 << 56;
+        let a8 = 1 << 7;
+        for corner in [a1, h1, a8] {
+            let result = Board::from_bitboards(player_board | corner, opponent_board, turn);
+            assert!(matches!(result, Err(BoardError::DisconnectedStones)));
         }
+        // h1 is connected through the diagonal
+        let diagonal = 0x0102040800000000;
+        assert!(Board::from_bitboards(player_board | diagonal, opponent_board, turn).is_ok());
+
+        // the board is not changed on error
+        let mut board = Board::from_transcript("f5d6").unwrap();
+        let before = board.get_board();
+        assert!(board
+            .try_set_board(player_board, player_board, Turn::White)
+            .is_err());
+        assert_eq!(board.get_board(), before);
+        board
+            .try_set_board(opponent_board, player_board, Turn::White)
+            .unwrap();
+        assert_eq!(
+            board.get_board(),
+            (opponent_board, player_board, Turn::White)
+        );
     }
 }
@@ -0,0 +1,452 @@
+#[cfg(test)]
+mod tests {
+    use rust_reversi_core::board::{format_square, parse_transcript, Board, BoardError, Turn};
+    use rust_reversi_core::formats::{
+        format_ggf, format_obf, parse_ggf, parse_ggf_games, parse_obf, parse_obf_lines,
+        read_wthor_players, read_wthor_tournaments, GgfError, GgfGame, GgfResult, GgfResultType,
+        ObfError, ObfPosition, WthorError, WthorReader,
+    };
+    use std::error::Error;
+    use std::io::Cursor;
+
+    const INITIAL_BO: &str =
+        "BO[8 -------- -------- -------- ---O*--- ---*O--- -------- -------- -------- *]";
+
+    fn random_game() -> Vec<Option<usize>> {
+        let mut board = Board::new();
+        let mut moves = Vec::new();
+        while !board.is_game_over() {
+            if board.is_pass() {
+                board.do_pass().unwrap();
+                moves.push(None);
+            } else {
+                let pos = board.get_random_move().unwrap();
+                board.do_move(pos).unwrap();
+                moves.push(Some(pos));
+            }
+        }
+        moves
+    }
+
+    const OBF_LINE: &str =
+        "--XXXXX--OOOXX-O-OOOXXOX-OXOXOXXOXXXOXXX--XOXOXX-XXXOOO--OOOOO-- X; G8:+18; H1:+12; H7:+6; A2:+0; A3:-4; B1:-4;";
+
+    fn wthor_header(game_count: u32, name_count: u16, year: u16) -> Vec<u8> {
+        let mut bytes = vec![20, 24, 1, 1];
+        bytes.extend_from_slice(&game_count.to_le_bytes());
+        bytes.extend_from_slice(&name_count.to_le_bytes());
+        bytes.extend_from_slice(&year.to_le_bytes());
+        bytes.extend_from_slice(&[8, 0, 22, 0]);
+        bytes
+    }
+
+    fn wthor_game(ids: (u16, u16, u16), scores: (u8, u8), moves: &[usize]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&ids.0.to_le_bytes());
+        bytes.extend_from_slice(&ids.1.to_le_bytes());
+        bytes.extend_from_slice(&ids.2.to_le_bytes());
+        bytes.push(scores.0);
+        bytes.push(scores.1);
+        for i in 0..60 {
+            bytes.push(match moves.get(i) {
+                Some(pos) => ((pos / 8 + 1) * 10 + pos % 8 + 1) as u8,
+                None => 0,
+            });
+        }
+        bytes
+    }
+
+    fn wthor_names(names: &[&[u8]], size: usize) -> Vec<u8> {
+        let mut bytes = wthor_header(0, names.len() as u16, 0);
+        for name in names {
+            let mut record = name.to_vec();
+            record.resize(size, 0);
+            bytes.extend(record);
+        }
+        bytes
+    }
+
+    #[test]
+    fn ggf_parse() {
+        let s = format!(
+            "(;GM[Othello]PC[GGS/os]DT[2003.12.15_13:24:03.MST]PB[alice]PW[bob]\
+             RB[2197.01]RW[2199.72]TI[05:00//02:00]TY[8]RE[+4.000:t]{}\
+             B[d3//0.01]W[c3/-1.00/0.02]B[B3]W[b2]B[f5]W[a3]B[a1]W[c1]B[PA]W[e3];)",
+            INITIAL_BO
+        );
+        let game = parse_ggf(&s).unwrap();
+        assert_eq!(game.get_black_player(), Some("alice"));
+        assert_eq!(game.get_white_player(), Some("bob"));
+        assert_eq!(game.get_time_control(), Some("05:00//02:00"));
+        assert_eq!(game.get_property("PC"), Some("GGS/os"));
+        assert_eq!(game.get_property("RB"), Some("2197.01"));
+        assert_eq!(game.get_property("XX"), None);
+        let result = game.get_result().unwrap();
+        assert_eq!(result.get_score(), 4.0);
+        assert_eq!(result.get_result_type(), GgfResultType::Timeout);
+        let expected = parse_transcript("d3c3b3b2f5a3a1c1PAe3").unwrap();
+        assert_eq!(game.get_moves(), expected.as_slice());
+        assert!(game.get_initial_board() == Board::new());
+
+        // the pass can be omitted
+        let implicit = s.replace("B[PA]", "");
+        assert_eq!(parse_ggf(&implicit).unwrap(), game);
+
+        let mut board = Board::new();
+        board.play_transcript("d3c3b3b2f5a3a1c1").unwrap();
+        board.do_pass().unwrap();
+        board.do_move(20).unwrap();
+        assert!(game.get_board() == board);
+    }
+
+    #[test]
+    fn ggf_setup_position() {
+        let board = Board::from_transcript("f5d6c3").unwrap();
+        let moves = parse_transcript("d3c4").unwrap();
+        let game = GgfGame::from_moves(&board, &moves).unwrap();
+        let s = format_ggf(&game).unwrap();
+        assert!(s.contains(" O]W[D3]B[C4];)"));
+
+        let parsed = parse_ggf(&s).unwrap();
+        assert!(parsed.get_initial_board() == board);
+        assert_eq!(parsed.get_initial_board().get_turn(), Turn::White);
+        assert_eq!(parsed, game);
+
+        // a missing BO means the initial position
+        let parsed = parse_ggf("(;GM[Othello]B[f5]W[d6];)").unwrap();
+        assert!(parsed.get_initial_board() == Board::new());
+        assert_eq!(parsed.get_moves(), &[Some(37), Some(43)]);
+    }
+
+    #[test]
+    fn ggf_round_trip() {
+        for _ in 0..20 {
+            let moves = random_game();
+            let mut game = GgfGame::from_moves(&Board::new(), &moves).unwrap();
+            let final_board = game.get_board();
+            assert!(final_board.is_game_over());
+            game.set_black_player(Some("black".to_string()));
+            game.set_white_player(Some("white".to_string()));
+            game.set_time_control(Some("01:00".to_string()));
+            game.set_property("DT", "2024.01.01").unwrap();
+            game.set_result(Some(GgfResult::new(
+                final_board.black_piece_num() as f64 - final_board.white_piece_num() as f64,
+                GgfResultType::Normal,
+            )));
+            let s = format_ggf(&game).unwrap();
+            assert!(s.starts_with("(;GM[Othello]DT[2024.01.01]PB[black]PW[white]TI[01:00]RE["));
+            assert_eq!(parse_ggf(&s).unwrap(), game);
+        }
+    }
+
+    #[test]
+    fn ggf_multiple_games() {
+        let games: Vec<GgfGame> = (0..3)
+            .map(|_| GgfGame::from_moves(&Board::new(), &random_game()).unwrap())
+            .collect();
+        let text = games
+            .iter()
+            .map(|g| format_ggf(g).unwrap())
+            .collect::<Vec<String>>()
+            .join("\n");
+        assert_eq!(parse_ggf_games(&text).unwrap(), games);
+        assert!(parse_ggf_games("").unwrap().is_empty());
+        assert!(matches!(
+            parse_ggf_games("(;GM[Othello]B[f5]"),
+            Err(GgfError::InvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn ggf_errors() {
+        assert!(matches!(
+            parse_ggf("GM[Othello]B[f5]"),
+            Err(GgfError::InvalidFormat)
+        ));
+        assert!(matches!(
+            parse_ggf("(;GM[Chess];)"),
+            Err(GgfError::InvalidFormat)
+        ));
+        assert!(matches!(
+            parse_ggf("(;GM[Othello]B[f5;)"),
+            Err(GgfError::InvalidFormat)
+        ));
+        assert!(matches!(
+            parse_ggf("(;GM[Othello]BO[8 --- *];)"),
+            Err(GgfError::InvalidBoard)
+        ));
+        assert!(matches!(
+            parse_ggf(&format!("(;GM[Othello]{};)", INITIAL_BO.replace('*', "?"))),
+            Err(GgfError::InvalidBoard)
+        ));
+        assert!(matches!(
+            parse_ggf("(;GM[Othello]RE[win];)"),
+            Err(GgfError::InvalidResult)
+        ));
+        assert!(matches!(
+            parse_ggf("(;GM[Othello]W[f5];)"),
+            Err(GgfError::UnexpectedColor)
+        ));
+        assert!(matches!(
+            parse_ggf("(;GM[Othello]B[a1];)"),
+            Err(GgfError::BoardError(BoardError::InvalidMove))
+        ));
+        assert!(matches!(
+            parse_ggf("(;GM[Othello]B[z9];)"),
+            Err(GgfError::BoardError(BoardError::InvalidNotation))
+        ));
+        assert!(matches!(
+            GgfGame::from_moves(&Board::new(), &[Some(0)]),
+            Err(GgfError::BoardError(BoardError::InvalidMove))
+        ));
+
+        let e: Box<dyn Error> = parse_ggf("(;GM[Othello]B[a1];)").unwrap_err().into();
+        assert_eq!(e.to_string(), "invalid move in GGF game: move is not legal");
+        assert!(e.source().unwrap().is::<BoardError>());
+        assert!(GgfError::InvalidFormat.source().is_none());
+
+        let mut game = GgfGame::default();
+        assert!(game.set_property("PB", "alice").is_err());
+        assert!(matches!(
+            parse_ggf("(;GM[Othello]PB[a\\];)"),
+            Err(GgfError::InvalidFormat)
+        ));
+
+        // ']' and '\\' are escaped
+        game.set_property("DT", "]").unwrap();
+        game.set_black_player(Some("[::1]:54321".to_string()));
+        game.set_white_player(Some("a\\]b".to_string()));
+        let s = format_ggf(&game).unwrap();
+        assert!(s.contains("DT[\\]]PB[[::1\\]:54321]PW[a\\\\\\]b]"));
+        assert_eq!(parse_ggf(&s).unwrap(), game);
+    }
+
+    #[test]
+    fn wthor_read_games() {
+        let random_moves = random_game();
+        let pass_moves = parse_transcript("d3c3b3b2f5a3a1c1PAe3").unwrap();
+        let encode = |moves: &[Option<usize>]| moves.iter().flatten().copied().collect::<Vec<_>>();
+        let mut bytes = wthor_header(3, 0, 2001);
+        bytes.extend(wthor_game((1, 0, 2), (40, 36), &encode(&random_moves)));
+        bytes.extend(wthor_game((0, 2, 1), (12, 20), &encode(&pass_moves)));
+        bytes.extend(wthor_game((5, 9, 9), (0, 0), &[0]));
+
+        let mut reader = WthorReader::new(Cursor::new(bytes)).unwrap();
+        assert_eq!(reader.get_year(), 2001);
+        assert_eq!(reader.get_depth(), 22);
+        assert_eq!(reader.get_game_count(), 3);
+        reader.set_players(vec![
+            "Alice".to_string(),
+            "Bob".to_string(),
+            "Hervé".to_string(),
+        ]);
+        reader.set_tournaments(vec!["Open".to_string(), "Cup".to_string()]);
+
+        let game = reader.next().unwrap().unwrap();
+        assert_eq!(game.get_moves(), random_moves.as_slice());
+        assert!(game.get_board().is_game_over());
+        assert_eq!(game.get_tournament(), Some("Cup"));
+        assert_eq!(game.get_black_player(), Some("Alice"));
+        assert_eq!(game.get_white_player(), Some("Hervé"));
+        assert_eq!(
+            (game.get_black_score(), game.get_theoretical_score()),
+            (40, 36)
+        );
+        assert_eq!(game.get_year(), 2001);
+
+        let game = reader.next().unwrap().unwrap();
+        assert_eq!(game.get_moves(), pass_moves.as_slice());
+        assert_eq!(game.get_moves()[8], None);
+        assert_eq!(game.get_tournament_id(), 0);
+        assert_eq!(game.get_black_player_id(), 2);
+        assert_eq!(game.get_white_player_id(), 1);
+
+        // a1 is illegal at the first move
+        assert!(matches!(
+            reader.next(),
+            Some(Err(WthorError::BoardError(BoardError::InvalidMove)))
+        ));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn wthor_read_errors() {
+        // truncated game
+        let mut bytes = wthor_header(2, 0, 2001);
+        bytes.extend(wthor_game((0, 0, 0), (32, 32), &[37]));
+        let results: Vec<_> = WthorReader::new(Cursor::new(bytes)).unwrap().collect();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(matches!(results[1], Err(WthorError::IoError(_))));
+
+        // invalid square
+        let mut bytes = wthor_header(1, 0, 2001);
+        let mut game = wthor_game((0, 0, 0), (32, 32), &[]);
+        game[8] = 19;
+        bytes.extend(game);
+        let mut reader = WthorReader::new(Cursor::new(bytes)).unwrap();
+        assert!(matches!(
+            reader.next(),
+            Some(Err(WthorError::BoardError(BoardError::InvalidPosition)))
+        ));
+
+        // 10x10 board
+        let mut bytes = wthor_header(0, 0, 2001);
+        bytes[12] = 10;
+        assert!(matches!(
+            WthorReader::new(Cursor::new(bytes)),
+            Err(WthorError::InvalidHeader)
+        ));
+        assert!(matches!(
+            WthorReader::new(Cursor::new(vec![0u8; 4])),
+            Err(WthorError::IoError(_))
+        ));
+
+        let e = WthorReader::new(Cursor::new(vec![0u8; 4])).err().unwrap();
+        assert!(e.to_string().starts_with("failed to read WTHOR data: "));
+        assert!(e.source().unwrap().is::<std::io::Error>());
+        assert_eq!(
+            WthorError::InvalidHeader.to_string(),
+            "malformed WTHOR header"
+        );
+    }
+
+    #[test]
+    fn wthor_read_names() {
+        let bytes = wthor_names(&[b"Alice", b"Herv\xe9 ", b""], 20);
+        let players = read_wthor_players(Cursor::new(bytes)).unwrap();
+        assert_eq!(players, vec!["Alice", "Hervé", ""]);
+
+        let bytes = wthor_names(&[b"Championnat de France"], 26);
+        let tournaments = read_wthor_tournaments(Cursor::new(bytes)).unwrap();
+        assert_eq!(tournaments, vec!["Championnat de France"]);
+
+        let mut bytes = wthor_names(&[b"Alice"], 20);
+        bytes.truncate(30);
+        assert!(matches!(
+            read_wthor_players(Cursor::new(bytes)),
+            Err(WthorError::IoError(_))
+        ));
+    }
+
+    #[test]
+    fn obf_parse() {
+        let position = parse_obf(OBF_LINE).unwrap();
+        let board = position.get_board();
+        let mut expected = Board::new();
+        expected
+            .set_board_str(
+                "--XXXXX--OOOXX-O-OOOXXOX-OXOXOXXOXXXOXXX--XOXOXX-XXXOOO--OOOOO--",
+                Turn::Black,
+            )
+            .unwrap();
+        assert!(board == expected);
+
+        let annotations = position.get_annotations();
+        assert_eq!(annotations.len(), 6);
+        assert_eq!(annotations[1].get_move(), Some(7));
+        assert_eq!(annotations[1].get_score(), Some(12));
+        assert_eq!(annotations[5].get_score(), Some(-4));
+        assert_eq!(position.get_best().unwrap().get_move(), Some(62));
+
+        // lowercase, '*', '.' and comments
+        let squares = OBF_LINE[..64].replace('X', "*").replace('-', ".");
+        let alternative = format!("{} x;g8:18;{} % ffo", squares, &OBF_LINE[75..]).to_lowercase();
+        assert_eq!(parse_obf(&alternative).unwrap(), position);
+
+        // no annotation and no ';'
+        let position = parse_obf(&OBF_LINE[..66]).unwrap();
+        assert!(position.get_annotations().is_empty());
+        assert!(position.get_best().is_none());
+    }
+
+    #[test]
+    fn obf_round_trip() {
+        let position = parse_obf(OBF_LINE).unwrap();
+        assert_eq!(format_obf(&position).unwrap(), OBF_LINE);
+
+        let mut board = Board::from_transcript("f5d6c3").unwrap();
+        let legal_moves = board.get_legal_moves_vec();
+        let mut position = ObfPosition::new(&board);
+        position
+            .add_annotation(Some(legal_moves[0]), Some(-2))
+            .unwrap();
+        position.add_annotation(Some(legal_moves[1]), None).unwrap();
+        let line = format_obf(&position).unwrap();
+        assert!(line.ends_with(&format!(
+            " O; {}:-2; {};",
+            format_square(legal_moves[0]).unwrap().to_uppercase(),
+            format_square(legal_moves[1]).unwrap().to_uppercase()
+        )));
+        let parsed = parse_obf(&line).unwrap();
+        assert_eq!(parsed, position);
+        assert_eq!(parsed.get_board().get_turn(), Turn::White);
+        assert_eq!(parsed.get_best().unwrap().get_move(), Some(legal_moves[0]));
+
+        // pass
+        let mut board = Board::new();
+        board.play_transcript("d3c3b3b2f5a3a1c1").unwrap();
+        let mut position = ObfPosition::new(&board);
+        position.add_annotation(None, Some(-10)).unwrap();
+        let line = format_obf(&position).unwrap();
+        assert!(line.ends_with(" X; PA:-10;"));
+        assert_eq!(parse_obf(&line).unwrap(), position);
+    }
+
+    #[test]
+    fn obf_lines() {
+        let text = format!(
+            "% endgame suite\n\n{}\n# comment\n{}\n",
+            OBF_LINE,
+            format_obf(&ObfPosition::new(&Board::new())).unwrap()
+        );
+        let positions = parse_obf_lines(&text).unwrap();
+        assert_eq!(positions.len(), 2);
+        assert!(positions[1].get_board() == Board::new());
+        assert!(matches!(
+            parse_obf_lines("--- X;"),
+            Err(ObfError::InvalidBoard)
+        ));
+    }
+
+    #[test]
+    fn obf_errors() {
+        let squares = &OBF_LINE[..64];
+        assert!(matches!(parse_obf(squares), Err(ObfError::InvalidBoard)));
+        assert!(matches!(
+            parse_obf(&format!("{} Z;", squares)),
+            Err(ObfError::InvalidBoard)
+        ));
+        assert!(matches!(
+            parse_obf(&format!("{}? X;", &squares[..63])),
+            Err(ObfError::InvalidBoard)
+        ));
+        assert!(matches!(
+            parse_obf(&format!("{} X; G8:+1x;", squares)),
+            Err(ObfError::InvalidFormat)
+        ));
+        assert!(matches!(
+            parse_obf(&format!("{} X; Z9:+1;", squares)),
+            Err(ObfError::InvalidFormat)
+        ));
+        assert!(matches!(
+            parse_obf(&format!("{} X; G8:+65;", squares)),
+            Err(ObfError::InvalidScore)
+        ));
+        assert!(matches!(
+            parse_obf(&format!("{} X; C1:+2;", squares)),
+            Err(ObfError::BoardError(BoardError::InvalidMove))
+        ));
+        assert!(matches!(
+            parse_obf(&format!("{} X; PA:+2;", squares)),
+            Err(ObfError::BoardError(BoardError::InvalidPass))
+        ));
+
+        let e: Box<dyn Error> = parse_obf(&format!("{} X; C1:+2;", squares))
+            .unwrap_err()
+            .into();
+        assert_eq!(e.to_string(), "invalid move in OBF line: move is not legal");
+        assert!(e.source().is_some());
+        assert!(ObfError::InvalidScore.source().is_none());
+    }
+}
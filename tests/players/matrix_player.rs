@@ -40,23 +40,25 @@ fn main() {
             }
             if rand::thread_rng().gen_bool(EPSILON) {
                 let m = board.get_random_move();
-                if m.is_err() {
-                    eprintln!("No legal moves");
-                    eprintln!("{}", input);
-                    return;
-                } else {
-                    println!("{}", m.unwrap());
+                match m {
+                    Ok(m) => println!("{}", m),
+                    Err(_) => {
+                        eprintln!("No legal moves");
+                        eprintln!("{}", input);
+                        return;
+                    }
                 }
             } else {
                 let evaluator = MatrixEvaluator::new(MATRIX);
                 let search = AlphaBetaSearch::new(depth, Arc::new(evaluator), 1 << 10);
                 let m = search.get_move(&mut board);
-                if m.is_none() {
-                    eprintln!("No legal moves");
-                    eprintln!("{}", input);
-                    return;
-                } else {
-                    println!("{}", m.unwrap());
+                match m {
+                    Some(m) => println!("{}", m),
+                    None => {
+                        eprintln!("No legal moves");
+                        eprintln!("{}", input);
+                        return;
+                    }
                 }
             }
         }
@@ -22,12 +22,13 @@ fn main() {
                 return;
             }
             let m = board.get_random_move();
-            if m.is_err() {
-                eprintln!("No legal moves");
-                eprintln!("{}", input);
-                return;
-            } else {
-                println!("{}", m.unwrap());
+            match m {
+                Ok(m) => println!("{}", m),
+                Err(_) => {
+                    eprintln!("No legal moves");
+                    eprintln!("{}", input);
+                    return;
+                }
             }
         }
     }
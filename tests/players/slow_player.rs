@@ -23,12 +23,13 @@ fn main() {
             }
             let m = board.get_random_move();
             thread::sleep(Duration::from_secs(5));
-            if m.is_err() {
-                eprintln!("No legal moves");
-                eprintln!("{}", input);
-                return;
-            } else {
-                println!("{}", m.unwrap());
+            match m {
+                Ok(m) => println!("{}", m),
+                Err(_) => {
+                    eprintln!("No legal moves");
+                    eprintln!("{}", input);
+                    return;
+                }
             }
         }
     }
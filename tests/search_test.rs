@@ -7,22 +7,29 @@ const EPSILON: f64 = 0.1;
 mod tests {
     use std::rc::Rc;
     use std::sync::Arc;
+    use std::thread;
     use std::time::Duration;
 
     use super::*;
     use players::compile_player;
     use players::get_player_path;
-    use rand::Rng;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
     use rust_reversi_core::arena::LocalArena;
     use rust_reversi_core::board::Board;
+    use rust_reversi_core::board::Move;
     use rust_reversi_core::board::Turn;
     use rust_reversi_core::search::AlphaBetaSearch;
     use rust_reversi_core::search::BitMatrixEvaluator;
     use rust_reversi_core::search::Evaluator;
+    use rust_reversi_core::search::LegalNumEvaluator;
     use rust_reversi_core::search::MatrixEvaluator;
+    use rust_reversi_core::search::MctsParallelMode;
     use rust_reversi_core::search::MctsSearch;
     use rust_reversi_core::search::PieceEvaluator;
+    use rust_reversi_core::search::PositionGenerator;
     use rust_reversi_core::search::Search;
+    use rust_reversi_core::search::SearchResult;
     use rust_reversi_core::search::ThunderSearch;
     use rust_reversi_core::search::WinrateEvaluator;
 
@@ -208,6 +215,377 @@ mod tests {
         }
     }
 
+    #[test]
+    fn position_generator() {
+        let lines = |generator: &mut PositionGenerator| -> Vec<String> {
+            generator
+                .generate_n(20)
+                .iter()
+                .map(|board| board.to_string())
+                .collect()
+        };
+        assert_eq!(
+            lines(&mut PositionGenerator::new(30, 1)),
+            lines(&mut PositionGenerator::new(30, 1))
+        );
+        assert_ne!(
+            lines(&mut PositionGenerator::new(30, 1)),
+            lines(&mut PositionGenerator::new(30, 2))
+        );
+
+        for n_empties in [1, 10, 30, 59, 60] {
+            let mut generator = PositionGenerator::new(n_empties, 0);
+            for board in generator.generate_n(10) {
+                assert_eq!(board.piece_sum(), 64 - n_empties as i32);
+                assert!(board.validate().is_ok());
+                assert!(!board.is_pass());
+            }
+        }
+        assert!(PositionGenerator::new(0, 0).generate().is_none());
+        assert!(PositionGenerator::new(61, 0).generate().is_none());
+
+        let mut generator = PositionGenerator::new(20, 3);
+        generator.set_balance_evaluator(Some(Arc::new(PieceEvaluator::new())));
+        generator.set_max_imbalance(2);
+        let boards = generator.generate_n(20);
+        assert_eq!(boards.len(), 20);
+        for board in boards {
+            assert!(board.diff_piece_num().abs() <= 2);
+        }
+
+        // weighted playouts are deterministic too
+        let weighted = |seed: u64| {
+            let mut generator = PositionGenerator::new(40, seed);
+            generator.set_playout_evaluator(Some(Arc::new(LegalNumEvaluator::new())));
+            generator.set_temperature(2.0);
+            lines(&mut generator)
+        };
+        assert_eq!(weighted(5), weighted(5));
+
+        // no position is accepted
+        let mut generator = PositionGenerator::new(20, 0);
+        generator.set_balance_evaluator(Some(Arc::new(PieceEvaluator::new())));
+        generator.set_max_imbalance(-1);
+        generator.set_max_attempts(5);
+        assert!(generator.generate().is_none());
+    }
+
+    #[test]
+    fn transposition_table() {
+        let search = AlphaBetaSearch::new(5, Arc::new(PieceEvaluator::new()), 1 << 10);
+        let mut no_tt_search = AlphaBetaSearch::new(5, Arc::new(PieceEvaluator::new()), 1 << 10);
+        no_tt_search.set_tt_size(0);
+        assert_eq!(search.get_tt_size(), 1 << 16);
+        assert_eq!(no_tt_search.get_tt_size(), 0);
+
+        let mut generator = PositionGenerator::new(40, 0);
+        for mut board in generator.generate_n(20) {
+            // transpositions are reached at the same depth, so the score does not change
+            let score = search.get_search_score(&mut board);
+            assert_eq!(score, no_tt_search.get_search_score(&mut board));
+            let stats = search.get_tt_stats();
+            assert!(stats.get_probes() > 0);
+            assert!(stats.get_stores() > 0);
+            assert!(stats.get_hits() <= stats.get_probes());
+            assert_eq!(no_tt_search.get_tt_stats().get_probes(), 0);
+            let m = search.get_move(&mut board).unwrap();
+            assert!(board.is_legal_move(m));
+        }
+
+        // iterative deepening reuses the results of the previous iterations
+        let mut board = Board::new();
+        search.get_move_with_timeout(&mut board, Duration::from_millis(50));
+        assert!(search.get_tt_stats().get_hit_rate() > 0.0);
+    }
+
+    #[test]
+    fn transposition_table_per_search() {
+        let mut search = AlphaBetaSearch::new(5, Arc::new(PieceEvaluator::new()), 1 << 10);
+        search.set_pvs(true);
+        let mut generator = PositionGenerator::new(30, 1);
+        let boards = generator.generate_n(5);
+
+        // back-to-back searches do not reuse the table of the previous one
+        for board in boards.iter() {
+            let first = search.search(&mut board.clone());
+            let first_stats = search.get_tt_stats();
+            let second = search.search(&mut board.clone());
+            assert_eq!(second.get_score(), first.get_score());
+            assert_eq!(second.get_pv(), first.get_pv());
+            assert_eq!(second.get_nodes(), first.get_nodes());
+            assert_eq!(search.get_tt_stats(), first_stats);
+        }
+
+        // searches at the same time on the same instance do not share the table
+        let expected: Vec<_> = boards
+            .iter()
+            .map(|board| search.search(&mut board.clone()))
+            .collect();
+        thread::scope(|scope| {
+            let handles: Vec<_> = boards
+                .iter()
+                .map(|board| {
+                    let search = &search;
+                    scope.spawn(move || search.search(&mut board.clone()))
+                })
+                .collect();
+            for (handle, expected) in handles.into_iter().zip(expected.iter()) {
+                let result = handle.join().unwrap();
+                assert_eq!(result.get_score(), expected.get_score());
+                assert_eq!(result.get_pv(), expected.get_pv());
+                assert_eq!(result.get_nodes(), expected.get_nodes());
+            }
+        });
+    }
+
+    #[test]
+    fn search_result() {
+        fn check_pv(board: &Board, result: &SearchResult) {
+            let mut board = board.clone();
+            for &mv in result.get_pv() {
+                board.play_move(mv).unwrap();
+            }
+        }
+
+        let search = AlphaBetaSearch::new(3, Arc::new(PieceEvaluator::new()), 1 << 10);
+        let mut generator = PositionGenerator::new(40, 1);
+        for mut board in generator.generate_n(10) {
+            let result = search.search(&mut board);
+            check_pv(&board, &result);
+            assert!(result.get_pv().len() >= 4);
+            assert_eq!(result.get_depth(), 4);
+            assert!(result.get_nodes() > 0);
+            assert!(!result.is_interrupted());
+            assert_eq!(result.get_best_move(), search.get_move(&mut board));
+            assert_eq!(result.get_score(), search.get_search_score(&mut board));
+        }
+
+        // the principal variation starts with the pass
+        let mut board = Board::from_transcript("d3c3b3b2f5a3a1c1").unwrap();
+        let result = search.search(&mut board);
+        check_pv(&board, &result);
+        assert_eq!(result.get_pv()[0], Move::Pass);
+        assert_eq!(result.get_best_move(), None);
+
+        let mut search = AlphaBetaSearch::new(60, Arc::new(PieceEvaluator::new()), 1 << 10);
+        search.set_margin_time(0.0);
+        let mut board = Board::new();
+        let result = search.search_with_timeout(&mut board, Duration::from_millis(20));
+        check_pv(&board, &result);
+        assert!(result.is_interrupted());
+        assert!(result.get_depth() > 0);
+        assert_eq!(result.get_pv().len(), result.get_depth());
+
+        let mut mcts = MctsSearch::new(1000, 1.0, 10);
+        mcts.set_seed(Some(0));
+        let result = mcts.search(&mut board);
+        check_pv(&board, &result);
+        assert!(!result.get_pv().is_empty());
+        assert_eq!(result.get_nodes(), 1000);
+        assert!(result.get_depth() >= result.get_pv().len());
+        assert_eq!(result.get_best_move(), mcts.get_move(&mut board));
+
+        #[derive(Debug)]
+        struct PieceWinrateEvaluator {}
+        impl WinrateEvaluator for PieceWinrateEvaluator {
+            fn evaluate(&self, board: &mut Board) -> f64 {
+                (board.diff_piece_num() as f64 + 64.0) / 128.0
+            }
+        }
+        let mut thunder = ThunderSearch::new(1000, 0.1, Arc::new(PieceWinrateEvaluator {}));
+        thunder.set_seed(Some(0));
+        let result = thunder.search(&mut board);
+        check_pv(&board, &result);
+        assert_eq!(result.get_nodes(), 1000);
+        assert_eq!(result.get_best_move(), thunder.get_move(&mut board));
+
+        // nothing to search after the game is over
+        let mut board = Board::new();
+        board.set_board(u64::MAX, 0, Turn::Black);
+        let result = mcts.search(&mut board);
+        assert!(result.get_pv().is_empty());
+        assert_eq!(result.get_score(), 1.0);
+    }
+
+    #[test]
+    fn pvs_and_aspiration() {
+        let evaluator = Arc::new(PieceEvaluator::new());
+        let search = AlphaBetaSearch::new(4, evaluator.clone(), 1 << 10);
+        let mut pvs_search = AlphaBetaSearch::new(4, evaluator.clone(), 1 << 10);
+        pvs_search.set_pvs(true);
+        assert!(pvs_search.get_pvs());
+        // iterations from depth 0 to 4 with the window as narrow as possible
+        let mut aspiration_search = AlphaBetaSearch::new(5, evaluator, 1 << 10);
+        aspiration_search.set_pvs(true);
+        aspiration_search.set_aspiration_window(Some(1));
+        assert_eq!(aspiration_search.get_aspiration_window(), Some(1));
+
+        let (mut nodes, mut pvs_nodes) = (0, 0);
+        for n_empties in [50, 40, 30, 20] {
+            let mut generator = PositionGenerator::new(n_empties, 2);
+            for mut board in generator.generate_n(10) {
+                let result = search.search(&mut board);
+                let pvs_result = pvs_search.search(&mut board);
+                assert_eq!(pvs_result.get_score(), result.get_score());
+                nodes += result.get_nodes();
+                pvs_nodes += pvs_result.get_nodes();
+
+                let aspiration_result =
+                    aspiration_search.search_with_timeout(&mut board, Duration::from_secs(60));
+                assert!(!aspiration_result.is_interrupted());
+                assert_eq!(aspiration_result.get_depth(), 5);
+                assert_eq!(aspiration_result.get_score(), result.get_score());
+                let mut child = board.clone();
+                for &mv in aspiration_result.get_pv() {
+                    child.play_move(mv).unwrap();
+                }
+            }
+        }
+        assert!(pvs_nodes < nodes);
+    }
+
+    #[test]
+    fn lazy_smp() {
+        let evaluator = Arc::new(PieceEvaluator::new());
+        let search = AlphaBetaSearch::new(4, evaluator.clone(), 1 << 10);
+        let mut smp_search = AlphaBetaSearch::new(4, evaluator, 1 << 10);
+        smp_search.set_n_threads(0);
+        assert_eq!(smp_search.get_n_threads(), 1);
+        smp_search.set_n_threads(4);
+        assert_eq!(smp_search.get_n_threads(), 4);
+
+        let mut generator = PositionGenerator::new(30, 3);
+        for mut board in generator.generate_n(10) {
+            let result = search.search(&mut board);
+            let smp_result = smp_search.search(&mut board);
+            assert_eq!(smp_result.get_score(), result.get_score());
+            assert!(board.is_legal_move(smp_result.get_best_move().unwrap()));
+        }
+
+        smp_search.set_max_depth(60);
+        let mut board = Board::new();
+        while !board.is_game_over() {
+            if board.is_pass() {
+                board.do_pass().unwrap();
+                continue;
+            }
+            let result = smp_search.search_with_timeout(&mut board, Duration::from_millis(20));
+            assert!(result.get_depth() > 0);
+            let mut child = board.clone();
+            for &mv in result.get_pv() {
+                child.play_move(mv).unwrap();
+            }
+            board.do_move(result.get_best_move().unwrap()).unwrap();
+        }
+    }
+
+    #[test]
+    fn lazy_smp_depth() {
+        // the helper threads only help when they run in parallel
+        let n_threads = std::thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(4);
+        if n_threads < 2 {
+            return;
+        }
+        let evaluator = Arc::new(PieceEvaluator::new());
+        let search = AlphaBetaSearch::new(60, evaluator.clone(), 1 << 10);
+        let mut smp_search = AlphaBetaSearch::new(60, evaluator, 1 << 10);
+        smp_search.set_n_threads(n_threads);
+
+        let mut depth = 0;
+        let mut smp_depth = 0;
+        let mut generator = PositionGenerator::new(40, 7);
+        for mut board in generator.generate_n(5) {
+            let timeout = Duration::from_millis(200);
+            depth += search.search_with_timeout(&mut board, timeout).get_depth();
+            smp_depth += smp_search
+                .search_with_timeout(&mut board, timeout)
+                .get_depth();
+        }
+        assert!(smp_depth >= depth);
+    }
+
+    #[test]
+    fn parallel_mcts() {
+        let mut search = MctsSearch::new(1000, 1.0, 10);
+        assert_eq!(search.get_n_threads(), 1);
+        assert_eq!(search.get_parallel_mode(), MctsParallelMode::Tree);
+        search.set_n_threads(0);
+        assert_eq!(search.get_n_threads(), 1);
+        search.set_n_threads(4);
+        search.set_seed(Some(0));
+
+        let mut generator = PositionGenerator::new(40, 5);
+        for mut board in generator.generate_n(5) {
+            for mode in [MctsParallelMode::Tree, MctsParallelMode::Root] {
+                search.set_parallel_mode(mode);
+                let result = search.search(&mut board);
+                assert_eq!(result.get_nodes(), 1000);
+                assert!(!result.is_interrupted());
+                assert!((0.0..=1.0).contains(&result.get_score()));
+                assert!(board.is_legal_move(result.get_best_move().unwrap()));
+                let mut child = board.clone();
+                for &mv in result.get_pv() {
+                    child.play_move(mv).unwrap();
+                }
+            }
+            // the trees of the root mode do not depend on the scheduling
+            let result = search.search(&mut board);
+            let again = search.search(&mut board);
+            assert_eq!(again.get_pv(), result.get_pv());
+            assert_eq!(again.get_score(), result.get_score());
+            assert_eq!(again.get_depth(), result.get_depth());
+
+            // the merged tree keeps the wins and visits of the tree of each thread
+            let mut single = MctsSearch::new(250, 1.0, 10);
+            let mut w = 0.0;
+            for thread_id in 0..4 {
+                single.set_seed(Some(thread_id));
+                w += single.search(&mut board).get_score() * 250.0;
+            }
+            assert!((result.get_score() - w / 1000.0).abs() < 1e-9);
+
+            search.set_parallel_mode(MctsParallelMode::Tree);
+            for n_playouts in [1, 7, 1000] {
+                search.set_n_playouts(n_playouts);
+                let result = search.search(&mut board);
+                assert_eq!(result.get_nodes(), n_playouts as u64);
+            }
+            search.set_n_playouts(1000);
+        }
+
+        search.set_n_playouts(usize::MAX);
+        search.set_margin_time(0.0);
+        for mode in [MctsParallelMode::Tree, MctsParallelMode::Root] {
+            search.set_parallel_mode(mode);
+            let mut board = Board::new();
+            let result = search.search_with_timeout(&mut board, Duration::from_millis(20));
+            assert!(result.is_interrupted());
+            assert!(result.get_nodes() > 0);
+            assert!(board.is_legal_move(result.get_best_move().unwrap()));
+        }
+    }
+
+    #[test]
+    fn typed_move() {
+        let search = AlphaBetaSearch::new(3, Arc::new(PieceEvaluator::new()), 1 << 10);
+        let mut board = Board::new();
+        while !board.is_game_over() {
+            let mv = search.get_typed_move(&mut board).unwrap();
+            assert_eq!(mv.is_pass(), board.is_pass());
+            if !mv.is_pass() {
+                assert_eq!(Option::<usize>::from(mv), search.get_move(&mut board));
+            }
+            let result = search.search(&mut board);
+            assert_eq!(result.get_best_typed_move(), Some(mv));
+            assert_eq!(result.get_best_move(), Option::<usize>::from(mv));
+            board.play_move(mv).unwrap();
+        }
+        assert_eq!(search.get_typed_move(&mut board), None);
+        assert_eq!(search.search(&mut board).get_best_typed_move(), None);
+    }
+
     #[test]
     fn bitmatrix_matrix_can_be_same() {
         let matrix = [
@@ -254,6 +632,81 @@ mod tests {
         }
     }
 
+    #[test]
+    fn seeded_search_is_reproducible() {
+        #[derive(Debug)]
+        struct PieceWinrateEvaluator {}
+        impl WinrateEvaluator for PieceWinrateEvaluator {
+            fn evaluate(&self, board: &mut Board) -> f64 {
+                (board.diff_piece_num() as f64 + 64.0) / 128.0
+            }
+        }
+        let mut mcts = MctsSearch::new(100, 1.0, 10);
+        mcts.set_seed(Some(42));
+        let mut thunder = ThunderSearch::new(100, 0.5, Arc::new(PieceWinrateEvaluator {}));
+        thunder.set_seed(Some(42));
+
+        let play = |seed: u64| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut board = Board::new();
+            let mut moves = Vec::new();
+            for _ in 0..20 {
+                if board.is_pass() {
+                    board.do_pass().unwrap();
+                    continue;
+                }
+                let m = mcts.get_move(&mut board).unwrap();
+                assert_eq!(mcts.get_move(&mut board), Some(m));
+                assert_eq!(
+                    mcts.get_search_score(&mut board),
+                    mcts.get_search_score(&mut board)
+                );
+                let t = thunder.get_move(&mut board).unwrap();
+                assert_eq!(thunder.get_move(&mut board), Some(t));
+                moves.push((m, t));
+                let r = board.get_random_move_with_rng(&mut rng).unwrap();
+                moves.push((r, r));
+                board.do_move(r).unwrap();
+            }
+            moves
+        };
+        assert_eq!(play(0), play(0));
+    }
+
+    #[test]
+    fn playout_search_without_playouts() {
+        #[derive(Debug)]
+        struct PieceWinrateEvaluator {}
+        impl WinrateEvaluator for PieceWinrateEvaluator {
+            fn evaluate(&self, board: &mut Board) -> f64 {
+                (board.diff_piece_num() as f64 + 64.0) / 128.0
+            }
+        }
+        let mut mcts = MctsSearch::new(0, 1.0, 10);
+        assert_eq!(mcts.get_n_playouts(), 1);
+        mcts.set_n_playouts(0);
+        assert_eq!(mcts.get_n_playouts(), 1);
+        let mut thunder = ThunderSearch::new(0, 0.5, Arc::new(PieceWinrateEvaluator {}));
+        assert_eq!(thunder.get_n_playouts(), 1);
+        thunder.set_n_playouts(0);
+        assert_eq!(thunder.get_n_playouts(), 1);
+
+        let mut board = Board::new();
+        for search in [&mcts as &dyn Search, &thunder] {
+            let result = search.search(&mut board);
+            assert_eq!(result.get_nodes(), 1);
+            assert!(!result.get_score().is_nan());
+            let m = search.get_move(&mut board).unwrap();
+            assert!(board.is_legal_move(m));
+        }
+        mcts.set_n_threads(4);
+        for mode in [MctsParallelMode::Tree, MctsParallelMode::Root] {
+            mcts.set_parallel_mode(mode);
+            let m = mcts.get_move(&mut board).unwrap();
+            assert!(board.is_legal_move(m));
+        }
+    }
+
     #[test]
     fn random_vs_mcts() {
         let timeout = std::time::Duration::from_millis(10);
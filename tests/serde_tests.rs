@@ -0,0 +1,121 @@
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use rust_reversi_core::arena::{OpeningStats, Termination};
+    use rust_reversi_core::board::{Board, Color, Move, Square, Turn};
+    use rust_reversi_core::search::{
+        BitMatrixEvaluator, Evaluator, MatrixEvaluator, PieceEvaluator, Search, SearchResult,
+    };
+    use std::sync::Arc;
+
+    const MATRIX: [[i32; 8]; 8] = [
+        [100, -20, 10, 5, 5, 10, -20, 100],
+        [-20, -50, -2, -2, -2, -2, -50, -20],
+        [10, -2, -1, -1, -1, -1, -2, 10],
+        [5, -2, -1, -1, -1, -1, -2, 5],
+        [5, -2, -1, -1, -1, -1, -2, 5],
+        [10, -2, -1, -1, -1, -1, -2, 10],
+        [-20, -50, -2, -2, -2, -2, -50, -20],
+        [100, -20, 10, 5, 5, 10, -20, 100],
+    ];
+
+    #[test]
+    fn serde_turn_and_color() {
+        let json = serde_json::to_string(&Turn::White).unwrap();
+        assert_eq!(json, "\"White\"");
+        assert_eq!(serde_json::from_str::<Turn>(&json).unwrap(), Turn::White);
+
+        let colors = vec![Color::Empty, Color::Black, Color::White];
+        let json = serde_json::to_string(&colors).unwrap();
+        assert_eq!(serde_json::from_str::<Vec<Color>>(&json).unwrap(), colors);
+    }
+
+    #[test]
+    fn serde_square_and_move() {
+        let square: Square = "f5".parse().unwrap();
+        let json = serde_json::to_string(&square).unwrap();
+        assert_eq!(json, "37");
+        assert_eq!(serde_json::from_str::<Square>(&json).unwrap(), square);
+        assert!(serde_json::from_str::<Square>("64").is_err());
+
+        let moves = vec![Move::Place(square), Move::Pass];
+        let json = serde_json::to_string(&moves).unwrap();
+        assert_eq!(serde_json::from_str::<Vec<Move>>(&json).unwrap(), moves);
+    }
+
+    #[test]
+    fn serde_search_result() {
+        let search = rust_reversi_core::search::AlphaBetaSearch::new(
+            2,
+            Arc::new(PieceEvaluator::new()),
+            1 << 10,
+        );
+        let result = search.search(&mut Board::new());
+        let json = serde_json::to_string(&result).unwrap();
+        assert_eq!(serde_json::from_str::<SearchResult>(&json).unwrap(), result);
+    }
+
+    #[test]
+    fn serde_board() {
+        let mut board = Board::from_transcript("f5d6c3d3").unwrap();
+        // fill the cache before serializing
+        board.get_legal_moves();
+        let json = serde_json::to_string(&board).unwrap();
+        let (player_board, opponent_board, turn) = board.get_board();
+        assert_eq!(
+            json,
+            format!(
+                "{{\"player_board\":{},\"opponent_board\":{},\"turn\":\"Black\"}}",
+                player_board, opponent_board
+            )
+        );
+        assert_eq!(turn, Turn::Black);
+
+        let mut restored: Board = serde_json::from_str(&json).unwrap();
+        assert!(restored == board);
+        assert_eq!(restored.get_hash(), board.get_hash());
+        assert_eq!(restored.get_legal_moves(), board.get_legal_moves());
+
+        assert!(serde_json::from_str::<Board>("{\"player_board\":1}").is_err());
+        let overlapping = "{\"player_board\":1,\"opponent_board\":1,\"turn\":\"Black\"}";
+        assert!(serde_json::from_str::<Board>(overlapping).is_err());
+    }
+
+    #[test]
+    fn serde_evaluators() {
+        let mut board = Board::from_transcript("f5d6c3d3c4").unwrap();
+
+        let evaluator = MatrixEvaluator::new(MATRIX);
+        let json = serde_json::to_string(&evaluator).unwrap();
+        let restored: MatrixEvaluator = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            restored.evaluate(&mut board),
+            evaluator.evaluate(&mut board)
+        );
+
+        let evaluator = evaluator.to_bit_matrix_evaluator();
+        let json = serde_json::to_string(&evaluator).unwrap();
+        let restored: BitMatrixEvaluator<10> = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            restored.evaluate(&mut board),
+            evaluator.evaluate(&mut board)
+        );
+        assert_eq!(restored.to_string(), evaluator.to_string());
+
+        // the number of weights must match N
+        assert!(serde_json::from_str::<BitMatrixEvaluator<9>>(&json).is_err());
+    }
+
+    #[test]
+    fn serde_arena_types() {
+        let termination = Termination::Timeout(Turn::Black);
+        let json = serde_json::to_string(&termination).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Termination>(&json).unwrap(),
+            termination
+        );
+
+        let stats = OpeningStats::default();
+        let json = serde_json::to_string(&stats).unwrap();
+        assert_eq!(serde_json::from_str::<OpeningStats>(&json).unwrap(), stats);
+    }
+}